@@ -2,6 +2,8 @@ use std::ops::{Index, IndexMut};
 
 use gl::types::GLuint;
 
+use super::state::{self, Slot};
+
 /// Vertex Array Object
 pub struct Array {
     array: GLuint,
@@ -24,6 +26,9 @@ impl Drop for Array {
         unsafe {
             gl::DeleteVertexArrays(1, &self.array);
         }
+        state::forget_name(self.array, |slot| *slot == Slot::VertexArray);
+        // Deleting the bound array reverts to array 0, and its element array buffer binding.
+        state::forget(Slot::Buffer(gl::ELEMENT_ARRAY_BUFFER));
     }
 }
 
@@ -116,16 +121,24 @@ impl Array {
     /// Bind this array in the current context.
     #[inline]
     pub fn bind(&self) {
-        unsafe {
-            gl::BindVertexArray(self.array);
-        }
+        Self::bind_name(self.array);
     }
 
     /// Unbind this array in the current context.
     #[inline]
     pub fn unbind() {
+        Self::bind_name(0);
+    }
+
+    #[inline]
+    fn bind_name(array: GLuint) {
+        if !state::update(Slot::VertexArray, &[array]) {
+            return;
+        }
         unsafe {
-            gl::BindVertexArray(0);
+            gl::BindVertexArray(array);
         }
+        // The element array buffer binding belongs to the vertex array.
+        state::forget(Slot::Buffer(gl::ELEMENT_ARRAY_BUFFER));
     }
 }
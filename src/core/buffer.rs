@@ -2,11 +2,15 @@ use std::ops::{Index, IndexMut};
 
 use gl::types::GLuint;
 
-use super::{enums::*, GlTypeT};
+use super::{
+    enums::*,
+    state::{self, Slot},
+    GlTypeT, PlainData,
+};
 
 /// Vertex Buffer Object
 pub struct Buffer {
-    buffer: GLuint,
+    pub(super) buffer: GLuint,
 }
 
 impl Buffer {
@@ -14,7 +18,7 @@ impl Buffer {
     pub(super) fn new() -> Self {
         let mut buffer = 0;
         unsafe {
-            gl::GenBuffers(1, &mut buffer);
+            gl::CreateBuffers(1, &mut buffer);
         }
         Self { buffer }
     }
@@ -26,6 +30,7 @@ impl Drop for Buffer {
         unsafe {
             gl::DeleteBuffers(1, &self.buffer);
         }
+        state::forget_name(self.buffer, |slot| matches!(slot, Slot::Buffer(_)));
     }
 }
 
@@ -38,9 +43,9 @@ impl Buffers {
     #[inline]
     pub(super) fn new(count: usize) -> Self {
         assert!(count > 0, "The number of buffers must be greater than 0");
-        let mut buffers = Vec::with_capacity(count);
+        let mut buffers = vec![0; count];
         unsafe {
-            gl::GenBuffers(count as _, buffers.as_mut_ptr());
+            gl::CreateBuffers(count as _, buffers.as_mut_ptr());
         }
         let buffers = buffers
             .into_iter()
@@ -116,16 +121,23 @@ impl Buffer {
     /// Bind this buffer in the current context.
     #[inline]
     pub fn bind(&self, target: Target) {
-        unsafe {
-            gl::BindBuffer(target.to_gl_target(), self.buffer);
-        }
+        Self::bind_name(target, self.buffer);
     }
 
     /// Unbind this buffer in the current context.
     #[inline]
     pub fn unbind(target: Target) {
+        Self::bind_name(target, 0);
+    }
+
+    #[inline]
+    fn bind_name(target: Target, buffer: GLuint) {
+        let target = target.to_gl_target();
+        if !state::update(Slot::Buffer(target), &[buffer]) {
+            return;
+        }
         unsafe {
-            gl::BindBuffer(target.to_gl_target(), 0);
+            gl::BindBuffer(target, buffer);
         }
     }
 
@@ -229,4 +241,33 @@ impl Buffer {
             );
         }
     }
+
+    /// Allocate `size` bytes of uninitialized storage for this buffer.
+    #[inline]
+    pub fn allocate(&self, size: usize, usage: Usage) {
+        unsafe {
+            gl::NamedBufferData(
+                self.buffer,
+                size as _,
+                std::ptr::null(),
+                usage.to_gl_usage(),
+            );
+        }
+    }
+
+    /// Wrapper of `glGetNamedBufferSubData(...)`
+    ///
+    /// Download a part of this buffer from `offset` into `data`.
+    #[inline]
+    #[allow(private_bounds)]
+    pub fn get_sub_data<T: PlainData>(&self, offset: usize, data: &mut [T]) {
+        unsafe {
+            gl::GetNamedBufferSubData(
+                self.buffer,
+                offset as _,
+                std::mem::size_of_val(data) as _,
+                data.as_mut_ptr() as _,
+            );
+        }
+    }
 }
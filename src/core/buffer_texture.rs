@@ -0,0 +1,111 @@
+use super::{check_error, Buffer, SizedFormat, TexTarget, Texture};
+
+/// Buffer texture object
+///
+/// A texture whose texels are stored in a `Buffer`, read by `texelFetch(...)`
+/// through a `samplerBuffer` in shaders.
+pub struct BufferTexture {
+    texture: Texture,
+    buffer: Buffer,
+    format: SizedFormat,
+    range: Option<(usize, usize)>,
+}
+
+impl BufferTexture {
+    /// Create a buffer texture, giving `buffer` back with the error on failure.
+    #[inline]
+    pub(super) fn new(
+        buffer: Buffer,
+        format: SizedFormat,
+        range: Option<(usize, usize)>,
+    ) -> Result<Self, (Buffer, String)> {
+        if let Err(err) = Self::check(format, range) {
+            return Err((buffer, err));
+        }
+        let texture = Texture::create(TexTarget::TexBuffer);
+        match range {
+            Some((offset, size)) => unsafe {
+                gl::TextureBufferRange(
+                    texture.id,
+                    format.to_gl_format(),
+                    buffer.buffer,
+                    offset as _,
+                    size as _,
+                );
+            },
+            None => unsafe {
+                gl::TextureBuffer(texture.id, format.to_gl_format(), buffer.buffer);
+            },
+        }
+        if let Err(err) = check_error("Buffer Texture Error") {
+            return Err((buffer, err));
+        }
+        Ok(Self {
+            texture,
+            buffer,
+            format,
+            range,
+        })
+    }
+
+    /// Check that `format` can be used by buffer textures and the `range` offset is aligned.
+    fn check(format: SizedFormat, range: Option<(usize, usize)>) -> Result<(), String> {
+        if !format.is_buffer_compatible() {
+            return Err(format!("{:?} can't be used by buffer textures", format));
+        }
+        if let Some((offset, _)) = range {
+            let alignment = Self::offset_alignment();
+            if offset % alignment != 0 {
+                return Err(format!(
+                    "Offset {} is not aligned to {} bytes",
+                    offset, alignment
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Return `GL_TEXTURE_BUFFER_OFFSET_ALIGNMENT`, the alignment in bytes of range offsets.
+    #[inline]
+    pub fn offset_alignment() -> usize {
+        let mut alignment = 0;
+        unsafe { gl::GetIntegerv(gl::TEXTURE_BUFFER_OFFSET_ALIGNMENT, &mut alignment) };
+        alignment.max(1) as _
+    }
+
+    /// Bind the texture to `TexTarget::TexBuffer`.
+    #[inline]
+    pub fn bind(&self) {
+        self.texture.bind(TexTarget::TexBuffer);
+    }
+
+    /// Return the texture of this buffer texture.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Return the buffer holding the texels.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Return the format of the texels.
+    #[inline]
+    pub fn format(&self) -> SizedFormat {
+        self.format
+    }
+
+    /// Return the `(offset, size)` in bytes of the used range, `None` for the whole buffer.
+    #[inline]
+    pub fn range(&self) -> Option<(usize, usize)> {
+        self.range
+    }
+
+    /// Release the texture and take the buffer back.
+    #[inline]
+    pub fn into_buffer(self) -> Buffer {
+        self.buffer
+    }
+}
@@ -1,6 +1,8 @@
 use super::{Texture, Textures};
 
 use super::enums::*;
+use super::pipeline::{self, Change, PipelineState};
+use super::state::{self, Slot};
 use std::{os::raw::c_void, ptr::null};
 
 pub struct Context {
@@ -95,12 +97,64 @@ impl Context {
         Texture::new()
     }
 
+    /// Create a new texture object bound to `target`.
+    ///
+    /// Unlike `new_texture(...)`, the texture can be used by the DSA functions
+    /// such as `Texture::storage_2d(...)` without binding it first.
+    #[inline]
+    pub fn create_texture(&self, target: TexTarget) -> Texture {
+        Texture::create(target)
+    }
+
     /// Create multiple new texture objeects.
     #[inline]
     pub fn new_textures(&self, count: usize) -> Textures {
         Textures::new(count)
     }
 
+    /// Create a buffer texture using `buffer` as its storage.
+    ///
+    /// It will return `Err((Buffer, String))`, giving `buffer` back,
+    /// if `format` can't be used by buffer textures.
+    #[inline]
+    pub fn new_buffer_texture(
+        &self,
+        buffer: super::Buffer,
+        format: SizedFormat,
+    ) -> Result<super::BufferTexture, (super::Buffer, String)> {
+        super::BufferTexture::new(buffer, format, None)
+    }
+
+    /// Create a buffer texture using `size` bytes of `buffer` from `offset` as its storage.
+    ///
+    /// It will return `Err((Buffer, String))`, giving `buffer` back,
+    /// if `format` can't be used by buffer textures,
+    /// or `offset` is not aligned to `GL_TEXTURE_BUFFER_OFFSET_ALIGNMENT`.
+    #[inline]
+    pub fn new_buffer_texture_range(
+        &self,
+        buffer: super::Buffer,
+        format: SizedFormat,
+        offset: usize,
+        size: usize,
+    ) -> Result<super::BufferTexture, (super::Buffer, String)> {
+        super::BufferTexture::new(buffer, format, Some((offset, size)))
+    }
+
+    /// Wrapper of `glFenceSync(...)`
+    ///
+    /// Insert a fence signaled once all previous commands have completed.
+    #[inline]
+    pub fn fence(&self) -> super::Fence {
+        super::Fence::new()
+    }
+
+    /// Create a new sampler object.
+    #[inline]
+    pub fn new_sampler(&self) -> super::Sampler {
+        super::Sampler::new()
+    }
+
     /// Create a new frame buffer object.
     pub fn new_frame_buffer(&self) -> super::FrameBuffer {
         super::FrameBuffer::new()
@@ -121,6 +175,207 @@ impl Context {
         super::RenderBuffers::new(count)
     }
 
+    /// Wrapper of `glBindSampler(...)`
+    ///
+    /// The sampler overrides the sampling parameters of the texture bound to `unit`.
+    #[inline]
+    pub fn bind_sampler(&self, unit: u32, sampler: &super::Sampler) {
+        unsafe {
+            gl::BindSampler(unit, sampler.id);
+        }
+    }
+
+    /// Wrapper of `glBindSampler(...)`
+    #[inline]
+    pub fn unbind_sampler(&self, unit: u32) {
+        unsafe {
+            gl::BindSampler(unit, 0);
+        }
+    }
+
+    /// Wrapper of `glBindImageTexture(...)`
+    ///
+    /// Bind the mipmap `level` of `texture` to the image `unit` for load/store
+    /// operations in shaders. It will return `Err(String)` if `format` can't be
+    /// used by images.
+    #[inline]
+    pub fn bind_image(
+        &self,
+        unit: u32,
+        texture: &Texture,
+        level: u32,
+        layer: ImageLayer,
+        access: Access,
+        format: SizedFormat,
+    ) -> Result<(), String> {
+        if !format.is_image_compatible() {
+            return Err(format!("{:?} is not an image format", format));
+        }
+        let (layered, layer) = match layer {
+            ImageLayer::Layered => (gl::TRUE, 0),
+            ImageLayer::Layer(layer) => (gl::FALSE, layer),
+        };
+        unsafe {
+            gl::BindImageTexture(
+                unit,
+                texture.id,
+                level as _,
+                layered,
+                layer as _,
+                access.to_gl_access(),
+                format.to_gl_format(),
+            );
+        }
+        super::check_error("Bind Image Error")
+    }
+
+    /// Wrapper of `glBindImageTexture(...)`
+    #[inline]
+    pub fn unbind_image(&self, unit: u32) {
+        unsafe {
+            gl::BindImageTexture(unit, 0, 0, gl::FALSE, 0, gl::READ_ONLY, gl::R8);
+        }
+    }
+
+    /// Wrapper of `glCopyImageSubData(...)`
+    ///
+    /// Copy a region of `size` texels from the mipmap `src_level` of `src` to `dst`,
+    /// where `z` is the layer of array textures or the face of cube maps.
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub fn copy_image(
+        &self,
+        src: &Texture,
+        src_level: u32,
+        (src_x, src_y, src_z): (u32, u32, u32),
+        dst: &Texture,
+        dst_level: u32,
+        (dst_x, dst_y, dst_z): (u32, u32, u32),
+        (width, height, depth): (u32, u32, u32),
+    ) -> Result<(), String> {
+        let src_target = src.target().ok_or("The source texture has no target")?;
+        let dst_target = dst
+            .target()
+            .ok_or("The destination texture has no target")?;
+        unsafe {
+            gl::CopyImageSubData(
+                src.id,
+                src_target.to_gl_target(),
+                src_level as _,
+                src_x as _,
+                src_y as _,
+                src_z as _,
+                dst.id,
+                dst_target.to_gl_target(),
+                dst_level as _,
+                dst_x as _,
+                dst_y as _,
+                dst_z as _,
+                width as _,
+                height as _,
+                depth as _,
+            );
+        }
+        super::check_error("Copy Image Error")
+    }
+
+    /// Return `GL_MAX_SAMPLES`, the maximum number of samples for multisampling.
+    #[inline]
+    pub fn max_samples(&self) -> u32 {
+        super::max_samples()
+    }
+
+    /// Return `GL_MAX_COLOR_ATTACHMENTS`, the maximum number of color attachments.
+    #[inline]
+    pub fn max_color_attachments(&self) -> u32 {
+        super::max_color_attachments()
+    }
+
+    /// Return `GL_MAX_DRAW_BUFFERS`, the maximum number of simultaneous fragment outputs.
+    #[inline]
+    pub fn max_draw_buffers(&self) -> u32 {
+        let mut max = 0;
+        unsafe { gl::GetIntegerv(gl::MAX_DRAW_BUFFERS, &mut max) };
+        max as _
+    }
+
+    /// Return `GL_UNPACK_ALIGNMENT`, the row alignment of pixels read from client memory.
+    #[inline]
+    pub fn unpack_alignment(&self) -> u32 {
+        let mut alignment = 0;
+        unsafe { gl::GetIntegerv(gl::UNPACK_ALIGNMENT, &mut alignment) };
+        alignment as _
+    }
+
+    /// Wrapper of `glPixelStorei(GL_UNPACK_ALIGNMENT, ...)`, which is 1, 2, 4 or 8.
+    #[inline]
+    pub fn set_unpack_alignment(&self, alignment: u32) {
+        unsafe { gl::PixelStorei(gl::UNPACK_ALIGNMENT, alignment as _) };
+    }
+
+    /// Enable the state cache of the current thread.
+    ///
+    /// State changes made through this crate are then tracked, and redundant ones are skipped.
+    /// Call `invalidate_state_cache()` after other code has changed the GL state.
+    #[inline]
+    pub fn enable_state_cache(&self) {
+        super::state::enable();
+    }
+
+    /// Disable the state cache, every state change is issued again.
+    #[inline]
+    pub fn disable_state_cache(&self) {
+        super::state::disable();
+    }
+
+    /// Return whether the state cache is enabled.
+    #[inline]
+    pub fn is_state_cache_enabled(&self) -> bool {
+        super::state::is_enabled()
+    }
+
+    /// Forget the tracked state, the next state changes are all issued.
+    #[inline]
+    pub fn invalidate_state_cache(&self) {
+        super::state::invalidate();
+        pipeline::set_last_applied(None);
+    }
+
+    /// Return the counters of issued and skipped state changes.
+    #[inline]
+    pub fn state_cache_stats(&self) -> super::StateCacheStats {
+        super::state::stats()
+    }
+
+    /// Reset the counters of the state cache.
+    #[inline]
+    pub fn reset_state_cache_stats(&self) {
+        super::state::reset_stats();
+    }
+
+    /// Wrapper of `glPushDebugGroup(...)`
+    ///
+    /// Open a named group of commands shown by debuggers and trace tools.
+    #[inline]
+    pub fn push_debug_group(&self, label: &str) {
+        unsafe {
+            gl::PushDebugGroup(
+                gl::DEBUG_SOURCE_APPLICATION,
+                0,
+                label.len() as _,
+                label.as_ptr() as _,
+            );
+        }
+    }
+
+    /// Wrapper of `glPopDebugGroup(...)`
+    #[inline]
+    pub fn pop_debug_group(&self) {
+        unsafe {
+            gl::PopDebugGroup();
+        }
+    }
+
     /// Wrapper of `glViewport(...)`.
     #[inline]
     pub fn view_port(&self, x: i32, y: i32, width: i32, height: i32) {
@@ -137,17 +392,52 @@ impl Context {
         }
     }
 
+    /// Wrapper of `glClearDepthf(...)`
+    #[inline]
+    pub fn clear_depth(&self, depth: f32) {
+        unsafe {
+            gl::ClearDepthf(depth);
+        }
+    }
+
+    /// Wrapper of `glClearStencil(...)`
+    #[inline]
+    pub fn clear_stencil(&self, stencil: i32) {
+        unsafe {
+            gl::ClearStencil(stencil);
+        }
+    }
+
     /// Wrapper of `glClear(...)`
     #[inline]
-    pub fn clear(&self, mask: u32) {
+    pub fn clear(&self, mask: ClearMask) {
         unsafe {
-            gl::Clear(mask);
+            gl::Clear(mask.to_gl_mask());
+        }
+    }
+
+    /// Set the clear values present in `values`, and clear those buffers.
+    #[inline]
+    pub fn clear_with(&self, values: ClearValues) {
+        if let Some([red, green, blue, alpha]) = values.color {
+            self.clear_color(red, green, blue, alpha);
+        }
+        if let Some(depth) = values.depth {
+            self.clear_depth(depth);
+        }
+        if let Some(stencil) = values.stencil {
+            self.clear_stencil(stencil);
         }
+        self.clear(values.mask());
     }
 
     /// Wrapper of `glEnable(...)`
     #[inline]
     pub fn enable(&self, cap: Cap) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::Cap(cap.to_gl_cap()), &[1]) {
+            return;
+        }
         unsafe {
             gl::Enable(cap.to_gl_cap());
         }
@@ -156,6 +446,10 @@ impl Context {
     /// Wrapper of `glDisable(...)`
     #[inline]
     pub fn disable(&self, cap: Cap) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::Cap(cap.to_gl_cap()), &[0]) {
+            return;
+        }
         unsafe {
             gl::Disable(cap.to_gl_cap());
         }
@@ -164,6 +458,10 @@ impl Context {
     /// Wrapper of `glDepthMask(...)`
     #[inline]
     pub fn depth_mask(&self, mask: bool) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::DepthMask, &[mask as _]) {
+            return;
+        }
         unsafe {
             gl::DepthMask(mask as _);
         }
@@ -172,6 +470,10 @@ impl Context {
     /// Wrapper of `glDepthFunc(...)`
     #[inline]
     pub fn depth_func(&self, func: DepthFunc) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::DepthFunc, &[func.to_gl_func()]) {
+            return;
+        }
         unsafe {
             gl::DepthFunc(func.to_gl_func());
         }
@@ -180,6 +482,10 @@ impl Context {
     /// Wrapper of `glStencilMask(...)`
     #[inline]
     pub fn stencil_mask(&self, mask: u32) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::StencilMask, &[mask]) {
+            return;
+        }
         unsafe {
             gl::StencilMask(mask);
         }
@@ -188,6 +494,10 @@ impl Context {
     /// Wrapper of `glStencilFunc(...)`
     #[inline]
     pub fn stencil_func(&self, func: StencilFunc, ref_: i32, mask: u32) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::StencilFunc, &[func.to_gl_func(), ref_ as _, mask]) {
+            return;
+        }
         unsafe {
             gl::StencilFunc(func.to_gl_func(), ref_, mask);
         }
@@ -196,6 +506,11 @@ impl Context {
     /// Wrapper of `glStencilOp(...)`
     #[inline]
     pub fn stencil_op(&self, fail: StencilOp, zfail: StencilOp, zpass: StencilOp) {
+        pipeline::set_last_applied(None);
+        let ops = [fail.to_gl_op(), zfail.to_gl_op(), zpass.to_gl_op()];
+        if !state::update(Slot::StencilOp, &ops) {
+            return;
+        }
         unsafe {
             gl::StencilOp(fail.to_gl_op(), zfail.to_gl_op(), zpass.to_gl_op());
         }
@@ -204,8 +519,13 @@ impl Context {
     /// Wrapper of `glBlendFunc(...)`
     #[inline]
     pub fn blend_func(&self, src: BlendFactor, dst: BlendFactor) {
+        pipeline::set_last_applied(None);
+        let (src, dst) = (src.to_gl_func(), dst.to_gl_func());
+        if !state::update(Slot::BlendFunc, &[src, dst, src, dst]) {
+            return;
+        }
         unsafe {
-            gl::BlendFunc(src.to_gl_func(), dst.to_gl_func());
+            gl::BlendFunc(src, dst);
         }
     }
 
@@ -218,6 +538,16 @@ impl Context {
         src_alpha: BlendFactor,
         dst_alpha: BlendFactor,
     ) {
+        pipeline::set_last_applied(None);
+        let funcs = [
+            src.to_gl_func(),
+            dst.to_gl_func(),
+            src_alpha.to_gl_func(),
+            dst_alpha.to_gl_func(),
+        ];
+        if !state::update(Slot::BlendFunc, &funcs) {
+            return;
+        }
         unsafe {
             gl::BlendFuncSeparate(
                 src.to_gl_func(),
@@ -231,14 +561,23 @@ impl Context {
     /// Wrapper of `glBlendEquation(...)`
     #[inline]
     pub fn blend_equation(&self, mode: BlendEquation) {
+        pipeline::set_last_applied(None);
+        let mode = mode.to_gl_equation();
+        if !state::update(Slot::BlendEquation, &[mode, mode]) {
+            return;
+        }
         unsafe {
-            gl::BlendEquation(mode.to_gl_equation());
+            gl::BlendEquation(mode);
         }
     }
 
     /// Wrapper of `glCullFace(...)`
     #[inline]
     pub fn cull_face(&self, face: CullFace) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::CullFace, &[face.to_gl_face()]) {
+            return;
+        }
         unsafe {
             gl::CullFace(face.to_gl_face());
         }
@@ -247,11 +586,386 @@ impl Context {
     /// Wrapper of `glFrontFace(...)`
     #[inline]
     pub fn front_face(&self, face: FrontFace) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::FrontFace, &[face.to_gl_face()]) {
+            return;
+        }
         unsafe {
             gl::FrontFace(face.to_gl_face());
         }
     }
 
+    /// Wrapper of `glColorMask(...)`
+    #[inline]
+    pub fn color_mask(&self, red: bool, green: bool, blue: bool, alpha: bool) {
+        pipeline::set_last_applied(None);
+        let mask = [red as u32, green as u32, blue as u32, alpha as u32];
+        if !state::update(Slot::ColorMask, &mask) {
+            return;
+        }
+        unsafe {
+            gl::ColorMask(red as _, green as _, blue as _, alpha as _);
+        }
+    }
+
+    /// Wrapper of `glPolygonMode(...)` for both faces.
+    #[inline]
+    pub fn polygon_mode(&self, mode: PolygonMode) {
+        pipeline::set_last_applied(None);
+        if !state::update(Slot::PolygonMode, &[mode.to_gl_mode()]) {
+            return;
+        }
+        unsafe {
+            gl::PolygonMode(gl::FRONT_AND_BACK, mode.to_gl_mode());
+        }
+    }
+
+    /// Wrapper of `glEnablei(...)`, e.g. the blending of draw buffer `index`.
+    #[inline]
+    pub fn enablei(&self, cap: IndexedCap, index: u32) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::Cap(cap.to_gl_cap()));
+        unsafe {
+            gl::Enablei(cap.to_gl_cap(), index);
+        }
+    }
+
+    /// Wrapper of `glDisablei(...)`, e.g. the blending of draw buffer `index`.
+    #[inline]
+    pub fn disablei(&self, cap: IndexedCap, index: u32) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::Cap(cap.to_gl_cap()));
+        unsafe {
+            gl::Disablei(cap.to_gl_cap(), index);
+        }
+    }
+
+    /// Wrapper of `glScissor(...)`
+    #[inline]
+    pub fn scissor(&self, x: i32, y: i32, width: i32, height: i32) {
+        unsafe {
+            gl::Scissor(x, y, width, height);
+        }
+    }
+
+    /// Wrapper of `glPolygonOffset(...)`
+    #[inline]
+    pub fn polygon_offset(&self, factor: f32, units: f32) {
+        unsafe {
+            gl::PolygonOffset(factor, units);
+        }
+    }
+
+    /// Wrapper of `glLineWidth(...)`
+    #[inline]
+    pub fn line_width(&self, width: f32) {
+        unsafe {
+            gl::LineWidth(width);
+        }
+    }
+
+    /// Wrapper of `glPointSize(...)`
+    #[inline]
+    pub fn point_size(&self, size: f32) {
+        unsafe {
+            gl::PointSize(size);
+        }
+    }
+
+    /// Wrapper of `glBlendColor(...)`
+    #[inline]
+    pub fn blend_color(&self, red: f32, green: f32, blue: f32, alpha: f32) {
+        unsafe {
+            gl::BlendColor(red, green, blue, alpha);
+        }
+    }
+
+    /// Wrapper of `glBlendEquationSeparate(...)`
+    #[inline]
+    pub fn blend_equation_separate(&self, rgb: BlendEquation, alpha: BlendEquation) {
+        pipeline::set_last_applied(None);
+        let (rgb, alpha) = (rgb.to_gl_equation(), alpha.to_gl_equation());
+        if !state::update(Slot::BlendEquation, &[rgb, alpha]) {
+            return;
+        }
+        unsafe {
+            gl::BlendEquationSeparate(rgb, alpha);
+        }
+    }
+
+    /// Wrapper of `glBlendFunci(...)`, setting the blending of one draw buffer.
+    #[inline]
+    pub fn blend_funci(&self, draw_buffer: u32, src: BlendFactor, dst: BlendFactor) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::BlendFunc);
+        unsafe {
+            gl::BlendFunci(draw_buffer, src.to_gl_func(), dst.to_gl_func());
+        }
+    }
+
+    /// Wrapper of `glBlendFuncSeparatei(...)`, setting the blending of one draw buffer.
+    #[inline]
+    pub fn blend_func_separatei(
+        &self,
+        draw_buffer: u32,
+        src: BlendFactor,
+        dst: BlendFactor,
+        src_alpha: BlendFactor,
+        dst_alpha: BlendFactor,
+    ) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::BlendFunc);
+        unsafe {
+            gl::BlendFuncSeparatei(
+                draw_buffer,
+                src.to_gl_func(),
+                dst.to_gl_func(),
+                src_alpha.to_gl_func(),
+                dst_alpha.to_gl_func(),
+            );
+        }
+    }
+
+    /// Wrapper of `glBlendEquationi(...)`, setting the blending of one draw buffer.
+    #[inline]
+    pub fn blend_equationi(&self, draw_buffer: u32, mode: BlendEquation) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::BlendEquation);
+        unsafe {
+            gl::BlendEquationi(draw_buffer, mode.to_gl_equation());
+        }
+    }
+
+    /// Wrapper of `glStencilFuncSeparate(...)`
+    #[inline]
+    pub fn stencil_func_separate(
+        &self,
+        face: StencilFace,
+        func: StencilFunc,
+        ref_: i32,
+        mask: u32,
+    ) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::StencilFunc);
+        unsafe {
+            gl::StencilFuncSeparate(face.to_gl_face(), func.to_gl_func(), ref_, mask);
+        }
+    }
+
+    /// Wrapper of `glStencilOpSeparate(...)`
+    #[inline]
+    pub fn stencil_op_separate(
+        &self,
+        face: StencilFace,
+        fail: StencilOp,
+        zfail: StencilOp,
+        zpass: StencilOp,
+    ) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::StencilOp);
+        unsafe {
+            gl::StencilOpSeparate(
+                face.to_gl_face(),
+                fail.to_gl_op(),
+                zfail.to_gl_op(),
+                zpass.to_gl_op(),
+            );
+        }
+    }
+
+    /// Wrapper of `glStencilMaskSeparate(...)`
+    #[inline]
+    pub fn stencil_mask_separate(&self, face: StencilFace, mask: u32) {
+        pipeline::set_last_applied(None);
+        state::forget(Slot::StencilMask);
+        unsafe {
+            gl::StencilMaskSeparate(face.to_gl_face(), mask);
+        }
+    }
+
+    /// Wrapper of `glDepthRangef(...)`
+    #[inline]
+    pub fn depth_range(&self, near: f32, far: f32) {
+        unsafe {
+            gl::DepthRangef(near, far);
+        }
+    }
+
+    /// Wrapper of `glClipControl(...)`, e.g. `ClipDepth::ZeroToOne` for reversed-Z.
+    #[inline]
+    pub fn clip_control(&self, origin: ClipOrigin, depth: ClipDepth) {
+        unsafe {
+            gl::ClipControl(origin.to_gl_origin(), depth.to_gl_depth());
+        }
+    }
+
+    /// Wrapper of `glPrimitiveRestartIndex(...)`, used when `Cap::PrimitiveRestart` is enabled.
+    #[inline]
+    pub fn primitive_restart_index(&self, index: u32) {
+        unsafe {
+            gl::PrimitiveRestartIndex(index);
+        }
+    }
+
+    /// Wrapper of `glSampleMaski(...)`, used when `Cap::SampleMask` is enabled.
+    #[inline]
+    pub fn sample_mask(&self, index: u32, mask: u32) {
+        unsafe {
+            gl::SampleMaski(index, mask);
+        }
+    }
+
+    /// Wrapper of `glLogicOp(...)`, used when `Cap::ColorLogicOp` is enabled.
+    #[inline]
+    pub fn logic_op(&self, op: LogicOp) {
+        unsafe {
+            gl::LogicOp(op.to_gl_op());
+        }
+    }
+
+    /// Wrapper of `glPatchParameteri(GL_PATCH_VERTICES, ...)`
+    #[inline]
+    pub fn patch_vertices(&self, count: i32) {
+        unsafe {
+            gl::PatchParameteri(gl::PATCH_VERTICES, count);
+        }
+    }
+
+    /// Enable `cap` until the returned guard is dropped, which restores its previous value.
+    #[inline]
+    pub fn scoped_enable(&self, cap: Cap) -> super::CapGuard<'_> {
+        let guard = super::CapGuard::new(cap.to_gl_cap());
+        self.enable(cap);
+        guard
+    }
+
+    /// Disable `cap` until the returned guard is dropped, which restores its previous value.
+    #[inline]
+    pub fn scoped_disable(&self, cap: Cap) -> super::CapGuard<'_> {
+        let guard = super::CapGuard::new(cap.to_gl_cap());
+        self.disable(cap);
+        guard
+    }
+
+    /// Set the viewport until the returned guard is dropped, which restores the previous one.
+    #[inline]
+    pub fn scoped_viewport(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> super::ViewportGuard<'_> {
+        let guard = super::ViewportGuard::new();
+        self.view_port(x, y, width, height);
+        guard
+    }
+
+    /// Set the blending, `None` to disable it, until the returned guard is dropped.
+    pub fn scoped_blend(&self, blend: Option<super::BlendState>) -> super::BlendGuard<'_> {
+        let guard = super::BlendGuard::new();
+        match blend {
+            Some(blend) => {
+                self.enable(Cap::Blend);
+                self.blend_func_separate(blend.src, blend.dst, blend.src_alpha, blend.dst_alpha);
+                self.blend_equation(blend.equation);
+            }
+            None => self.disable(Cap::Blend),
+        }
+        guard
+    }
+
+    /// Set the depth test, `None` to disable it, until the returned guard is dropped.
+    pub fn scoped_depth(&self, depth: Option<super::DepthState>) -> super::DepthGuard<'_> {
+        let guard = super::DepthGuard::new();
+        match depth {
+            Some(depth) => {
+                self.enable(Cap::DepthTest);
+                self.depth_func(depth.func);
+                self.depth_mask(depth.write);
+            }
+            None => self.disable(Cap::DepthTest),
+        }
+        guard
+    }
+
+    /// Set the face culling, `None` to disable it, until the returned guard is dropped.
+    pub fn scoped_cull(&self, face: Option<CullFace>) -> super::CullGuard<'_> {
+        let guard = super::CullGuard::new();
+        match face {
+            Some(face) => {
+                self.enable(Cap::CullFace);
+                self.cull_face(face);
+            }
+            None => self.disable(Cap::CullFace),
+        }
+        guard
+    }
+
+    /// Set the color write mask until the returned guard is dropped.
+    #[inline]
+    pub fn scoped_color_mask(
+        &self,
+        red: bool,
+        green: bool,
+        blue: bool,
+        alpha: bool,
+    ) -> super::ColorMaskGuard<'_> {
+        let guard = super::ColorMaskGuard::new();
+        self.color_mask(red, green, blue, alpha);
+        guard
+    }
+
+    /// Set the depth write mask until the returned guard is dropped.
+    #[inline]
+    pub fn scoped_depth_mask(&self, mask: bool) -> super::DepthMaskGuard<'_> {
+        let guard = super::DepthMaskGuard::new();
+        self.depth_mask(mask);
+        guard
+    }
+
+    /// Set the stencil write mask of both faces until the returned guard is dropped.
+    #[inline]
+    pub fn scoped_stencil_mask(&self, mask: u32) -> super::StencilMaskGuard<'_> {
+        let guard = super::StencilMaskGuard::new();
+        self.stencil_mask(mask);
+        guard
+    }
+
+    /// Apply `state`, issuing only the changes from the last applied state.
+    ///
+    /// Any state change made by other methods since then makes the next call apply everything.
+    pub fn apply(&self, state: &PipelineState) {
+        let last = pipeline::last_applied();
+        if last.as_ref() == Some(state) {
+            return;
+        }
+        pipeline::diff(last.as_ref(), state, |change| match change {
+            Change::Enable(cap) => self.enable(cap),
+            Change::Disable(cap) => self.disable(cap),
+            Change::Blend(blend) => {
+                self.blend_func_separate(blend.src, blend.dst, blend.src_alpha, blend.dst_alpha);
+                self.blend_equation(blend.equation);
+            }
+            Change::Depth(depth) => {
+                self.depth_func(depth.func);
+                self.depth_mask(depth.write);
+            }
+            Change::Stencil(stencil) => {
+                self.stencil_func(stencil.func, stencil.reference, stencil.read_mask);
+                self.stencil_op(stencil.fail, stencil.depth_fail, stencil.pass);
+                self.stencil_mask(stencil.write_mask);
+            }
+            Change::CullFace(face) => self.cull_face(face),
+            Change::FrontFace(face) => self.front_face(face),
+            Change::ColorMask([red, green, blue, alpha]) => {
+                self.color_mask(red, green, blue, alpha)
+            }
+            Change::PolygonMode(mode) => self.polygon_mode(mode),
+        });
+        pipeline::set_last_applied(Some(*state));
+    }
+
     /// Wrapper of `glDrawArrays(...)`
     #[inline]
     pub fn draw_arrays(&self, mode: Mode, first: i32, count: i32) {
@@ -87,9 +87,90 @@ pub enum Usage {
 pub struct Mask;
 
 impl Mask {
-    pub const COLOR_BUFFER_BIT: u32 = gl::COLOR_BUFFER_BIT;
-    pub const DEPTH_BUFFER_BIT: u32 = gl::DEPTH_BUFFER_BIT;
-    pub const STENCIL_BUFFER_BIT: u32 = gl::STENCIL_BUFFER_BIT;
+    pub const COLOR_BUFFER_BIT: ClearMask = ClearMask::COLOR;
+    pub const DEPTH_BUFFER_BIT: ClearMask = ClearMask::DEPTH;
+    pub const STENCIL_BUFFER_BIT: ClearMask = ClearMask::STENCIL;
+}
+
+/// Set of buffers to clear or blit, combined by `|`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ClearMask(GLenum);
+
+impl ClearMask {
+    pub const NONE: ClearMask = ClearMask(0);
+    pub const COLOR: ClearMask = ClearMask(gl::COLOR_BUFFER_BIT);
+    pub const DEPTH: ClearMask = ClearMask(gl::DEPTH_BUFFER_BIT);
+    pub const STENCIL: ClearMask = ClearMask(gl::STENCIL_BUFFER_BIT);
+    pub const ALL: ClearMask =
+        ClearMask(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT | gl::STENCIL_BUFFER_BIT);
+
+    /// Return whether all buffers of `other` are in this mask.
+    #[inline]
+    pub const fn contains(self, other: ClearMask) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Return whether no buffer is in this mask.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    #[inline]
+    pub(super) const fn to_gl_mask(self) -> GLenum {
+        self.0
+    }
+}
+
+impl std::ops::BitOr for ClearMask {
+    type Output = ClearMask;
+
+    #[inline]
+    fn bitor(self, rhs: ClearMask) -> ClearMask {
+        ClearMask(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for ClearMask {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: ClearMask) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl std::ops::BitAnd for ClearMask {
+    type Output = ClearMask;
+
+    #[inline]
+    fn bitand(self, rhs: ClearMask) -> ClearMask {
+        ClearMask(self.0 & rhs.0)
+    }
+}
+
+/// Values of `Context::clear_with(...)`, a buffer is cleared only when its value is `Some`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ClearValues {
+    pub color: Option<[f32; 4]>,
+    pub depth: Option<f32>,
+    pub stencil: Option<i32>,
+}
+
+impl ClearValues {
+    /// Return the buffers cleared by these values.
+    #[inline]
+    pub const fn mask(&self) -> ClearMask {
+        let mut mask = 0;
+        if self.color.is_some() {
+            mask |= gl::COLOR_BUFFER_BIT;
+        }
+        if self.depth.is_some() {
+            mask |= gl::DEPTH_BUFFER_BIT;
+        }
+        if self.stencil.is_some() {
+            mask |= gl::STENCIL_BUFFER_BIT;
+        }
+        ClearMask(mask)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -102,7 +183,19 @@ pub enum ShaderType {
     Compute,
 }
 
-use gl::types::{GLenum, GLfloat, GLint};
+use gl::types::{GLenum, GLfloat, GLint, GLuint};
+
+// `EXT_texture_compression_s3tc` and `EXT_texture_sRGB` are not part of the core profile bindings.
+const COMPRESSED_RGB_S3TC_DXT1_EXT: GLenum = 0x83F0;
+const COMPRESSED_RGBA_S3TC_DXT1_EXT: GLenum = 0x83F1;
+const COMPRESSED_RGBA_S3TC_DXT3_EXT: GLenum = 0x83F2;
+const COMPRESSED_RGBA_S3TC_DXT5_EXT: GLenum = 0x83F3;
+const COMPRESSED_SRGB_S3TC_DXT1_EXT: GLenum = 0x8C4C;
+const COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT: GLenum = 0x8C4D;
+const COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT: GLenum = 0x8C4E;
+const COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT: GLenum = 0x8C4F;
+// Core since OpenGL 4.6, `EXT_texture_filter_anisotropic` before.
+const TEXTURE_MAX_ANISOTROPY: GLenum = 0x84FE;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum DepthMode {
@@ -163,6 +256,7 @@ pub enum Wrap {
     MirrorClampToEdge,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TexParam {
     DepthStencilMode(DepthMode),
     BaseLevel(i32),
@@ -182,12 +276,46 @@ pub enum TexParam {
     WrapS(Wrap),
     WrapT(Wrap),
     WrapR(Wrap),
+    BorderColor(f32, f32, f32, f32),
+    BorderColorI(i32, i32, i32, i32),
+    BorderColorUI(u32, u32, u32, u32),
+    MaxAnisotropy(f32),
+}
+
+/// Name of a `TexParam` without its value, used to query the current value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TexParamKind {
+    DepthStencilMode,
+    BaseLevel,
+    CompareFunc,
+    CompareMode,
+    LodBias,
+    MinFilter,
+    MagFilter,
+    MinLod,
+    MaxLod,
+    MaxLevel,
+    SwizzleR,
+    SwizzleG,
+    SwizzleB,
+    SwizzleA,
+    SwizzleRGBA,
+    WrapS,
+    WrapT,
+    WrapR,
+    BorderColor,
+    BorderColorI,
+    BorderColorUI,
+    MaxAnisotropy,
 }
 
 pub(super) enum TexParamPair {
     GLf(GLenum, GLfloat),
     GLi(GLenum, GLint),
     GLiv(GLenum, [GLint; 4]),
+    GLfv(GLenum, [GLfloat; 4]),
+    GLIiv(GLenum, [GLint; 4]),
+    GLIuiv(GLenum, [GLuint; 4]),
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -241,6 +369,12 @@ pub enum ImageFormat {
     BGR,
     RGBA,
     BGRA,
+    RedInteger,
+    RGInteger,
+    RGBInteger,
+    BGRInteger,
+    RGBAInteger,
+    BGRAInteger,
     DepthComponent,
     DepthStencil,
 }
@@ -338,8 +472,17 @@ pub enum CompressedFormat {
     SRGB_ALPHA_BPTC_UNORM,
     RGB_BPTC_SIGNED_FLOAT,
     RGB_BPTC_UNSIGNED_FLOAT,
+    RGB_S3TC_DXT1,
+    RGBA_S3TC_DXT1,
+    RGBA_S3TC_DXT3,
+    RGBA_S3TC_DXT5,
+    SRGB_S3TC_DXT1,
+    SRGB_ALPHA_S3TC_DXT1,
+    SRGB_ALPHA_S3TC_DXT3,
+    SRGB_ALPHA_S3TC_DXT5,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum InternalFormat {
     Base(BaseFormat),
     Sized(SizedFormat),
@@ -373,6 +516,7 @@ pub enum PixelDataType {
 
 pub type DepthFunc = CompareFunc;
 pub type StencilFunc = CompareFunc;
+pub type StencilFace = CullFace;
 
 impl TexParam {
     const fn swizzle(swizzle: Swizzle) -> i32 {
@@ -397,7 +541,7 @@ impl TexParam {
     }
 
     #[inline]
-    pub(super) const fn to_pair(&self) -> TexParamPair {
+    pub(super) const fn to_pair(self) -> TexParamPair {
         match self {
             TexParam::DepthStencilMode(depth_mode) => match depth_mode {
                 DepthMode::Component => {
@@ -407,7 +551,7 @@ impl TexParam {
                     TexParamPair::GLi(gl::DEPTH_STENCIL_TEXTURE_MODE, gl::STENCIL_INDEX as _)
                 }
             },
-            TexParam::BaseLevel(level) => TexParamPair::GLi(gl::TEXTURE_BASE_LEVEL, *level),
+            TexParam::BaseLevel(level) => TexParamPair::GLi(gl::TEXTURE_BASE_LEVEL, level),
             TexParam::CompareFunc(compare_func) => {
                 TexParamPair::GLi(gl::TEXTURE_COMPARE_FUNC, compare_func.to_gl_func() as _)
             }
@@ -417,7 +561,7 @@ impl TexParam {
                 }
                 CompareMode::None => TexParamPair::GLi(gl::TEXTURE_COMPARE_MODE, gl::NONE as _),
             },
-            TexParam::LodBias(value) => TexParamPair::GLf(gl::TEXTURE_LOD_BIAS, *value),
+            TexParam::LodBias(value) => TexParamPair::GLf(gl::TEXTURE_LOD_BIAS, value),
             TexParam::MinFilter(min_filter) => match min_filter {
                 MinFilter::Nearest => TexParamPair::GLi(gl::TEXTURE_MIN_FILTER, gl::NEAREST as _),
                 MinFilter::Linear => TexParamPair::GLi(gl::TEXTURE_MIN_FILTER, gl::LINEAR as _),
@@ -438,38 +582,202 @@ impl TexParam {
                 MagFilter::Nearest => TexParamPair::GLi(gl::TEXTURE_MAG_FILTER, gl::NEAREST as _),
                 MagFilter::Linear => TexParamPair::GLi(gl::TEXTURE_MAG_FILTER, gl::LINEAR as _),
             },
-            TexParam::MinLod(value) => TexParamPair::GLf(gl::TEXTURE_MIN_LOD, *value),
-            TexParam::MaxLod(value) => TexParamPair::GLf(gl::TEXTURE_MAX_LOD, *value),
-            TexParam::MaxLevel(value) => TexParamPair::GLi(gl::TEXTURE_MAX_LEVEL, *value),
+            TexParam::MinLod(value) => TexParamPair::GLf(gl::TEXTURE_MIN_LOD, value),
+            TexParam::MaxLod(value) => TexParamPair::GLf(gl::TEXTURE_MAX_LOD, value),
+            TexParam::MaxLevel(value) => TexParamPair::GLi(gl::TEXTURE_MAX_LEVEL, value),
             TexParam::SwizzleR(swizzle) => {
-                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_R, Self::swizzle(*swizzle))
+                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_R, Self::swizzle(swizzle))
             }
             TexParam::SwizzleG(swizzle) => {
-                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_G, Self::swizzle(*swizzle))
+                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_G, Self::swizzle(swizzle))
             }
             TexParam::SwizzleB(swizzle) => {
-                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_B, Self::swizzle(*swizzle))
+                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_B, Self::swizzle(swizzle))
             }
             TexParam::SwizzleA(swizzle) => {
-                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_A, Self::swizzle(*swizzle))
+                TexParamPair::GLi(gl::TEXTURE_SWIZZLE_A, Self::swizzle(swizzle))
             }
             TexParam::SwizzleRGBA(sr, sg, sb, sa) => TexParamPair::GLiv(
                 gl::TEXTURE_SWIZZLE_RGBA,
                 [
-                    Self::swizzle(*sr),
-                    Self::swizzle(*sg),
-                    Self::swizzle(*sb),
-                    Self::swizzle(*sa),
+                    Self::swizzle(sr),
+                    Self::swizzle(sg),
+                    Self::swizzle(sb),
+                    Self::swizzle(sa),
                 ],
             ),
-            TexParam::WrapS(wrap) => TexParamPair::GLi(gl::TEXTURE_WRAP_S, Self::wrap(*wrap)),
-            TexParam::WrapT(wrap) => TexParamPair::GLi(gl::TEXTURE_WRAP_T, Self::wrap(*wrap)),
-            TexParam::WrapR(wrap) => TexParamPair::GLi(gl::TEXTURE_WRAP_R, Self::wrap(*wrap)),
+            TexParam::WrapS(wrap) => TexParamPair::GLi(gl::TEXTURE_WRAP_S, Self::wrap(wrap)),
+            TexParam::WrapT(wrap) => TexParamPair::GLi(gl::TEXTURE_WRAP_T, Self::wrap(wrap)),
+            TexParam::WrapR(wrap) => TexParamPair::GLi(gl::TEXTURE_WRAP_R, Self::wrap(wrap)),
+            TexParam::BorderColor(r, g, b, a) => {
+                TexParamPair::GLfv(gl::TEXTURE_BORDER_COLOR, [r, g, b, a])
+            }
+            TexParam::BorderColorI(r, g, b, a) => {
+                TexParamPair::GLIiv(gl::TEXTURE_BORDER_COLOR, [r, g, b, a])
+            }
+            TexParam::BorderColorUI(r, g, b, a) => {
+                TexParamPair::GLIuiv(gl::TEXTURE_BORDER_COLOR, [r, g, b, a])
+            }
+            TexParam::MaxAnisotropy(value) => TexParamPair::GLf(TEXTURE_MAX_ANISOTROPY, value),
+        }
+    }
+
+    fn swizzle_from(value: i32) -> Option<Swizzle> {
+        match value as GLenum {
+            gl::RED => Some(Swizzle::Red),
+            gl::GREEN => Some(Swizzle::Green),
+            gl::BLUE => Some(Swizzle::Blue),
+            gl::ALPHA => Some(Swizzle::Alpha),
+            gl::ZERO => Some(Swizzle::Zero),
+            gl::ONE => Some(Swizzle::One),
+            _ => None,
+        }
+    }
+
+    fn wrap_from(value: i32) -> Option<Wrap> {
+        match value as GLenum {
+            gl::CLAMP_TO_EDGE => Some(Wrap::ClampToEdge),
+            gl::CLAMP_TO_BORDER => Some(Wrap::ClampToBorder),
+            gl::MIRRORED_REPEAT => Some(Wrap::MirroredRepeat),
+            gl::REPEAT => Some(Wrap::Repeat),
+            gl::MIRROR_CLAMP_TO_EDGE => Some(Wrap::MirrorClampToEdge),
+            _ => None,
+        }
+    }
+
+    /// Decode the values returned by `glGet{Texture|Sampler}Parameter*(...)`.
+    pub(super) fn from_raw(kind: TexParamKind, ints: [i32; 4], floats: [f32; 4]) -> Option<Self> {
+        let [i0, i1, i2, i3] = ints;
+        let [f0, f1, f2, f3] = floats;
+        Some(match kind {
+            TexParamKind::DepthStencilMode => TexParam::DepthStencilMode(match i0 as GLenum {
+                gl::DEPTH_COMPONENT => DepthMode::Component,
+                gl::STENCIL_INDEX => DepthMode::Index,
+                _ => return None,
+            }),
+            TexParamKind::BaseLevel => TexParam::BaseLevel(i0),
+            TexParamKind::CompareFunc => TexParam::CompareFunc(CompareFunc::from_gl_func(i0 as _)?),
+            TexParamKind::CompareMode => TexParam::CompareMode(match i0 as GLenum {
+                gl::COMPARE_REF_TO_TEXTURE => CompareMode::CompareRefToTexture,
+                gl::NONE => CompareMode::None,
+                _ => return None,
+            }),
+            TexParamKind::LodBias => TexParam::LodBias(f0),
+            TexParamKind::MinFilter => TexParam::MinFilter(match i0 as GLenum {
+                gl::NEAREST => MinFilter::Nearest,
+                gl::LINEAR => MinFilter::Linear,
+                gl::NEAREST_MIPMAP_NEAREST => MinFilter::NearestMipmapNearest,
+                gl::LINEAR_MIPMAP_NEAREST => MinFilter::LinearMipmapNearest,
+                gl::NEAREST_MIPMAP_LINEAR => MinFilter::NearestMipmapLinear,
+                gl::LINEAR_MIPMAP_LINEAR => MinFilter::LinearMipmapLinear,
+                _ => return None,
+            }),
+            TexParamKind::MagFilter => TexParam::MagFilter(match i0 as GLenum {
+                gl::NEAREST => MagFilter::Nearest,
+                gl::LINEAR => MagFilter::Linear,
+                _ => return None,
+            }),
+            TexParamKind::MinLod => TexParam::MinLod(f0),
+            TexParamKind::MaxLod => TexParam::MaxLod(f0),
+            TexParamKind::MaxLevel => TexParam::MaxLevel(i0),
+            TexParamKind::SwizzleR => TexParam::SwizzleR(Self::swizzle_from(i0)?),
+            TexParamKind::SwizzleG => TexParam::SwizzleG(Self::swizzle_from(i0)?),
+            TexParamKind::SwizzleB => TexParam::SwizzleB(Self::swizzle_from(i0)?),
+            TexParamKind::SwizzleA => TexParam::SwizzleA(Self::swizzle_from(i0)?),
+            TexParamKind::SwizzleRGBA => TexParam::SwizzleRGBA(
+                Self::swizzle_from(i0)?,
+                Self::swizzle_from(i1)?,
+                Self::swizzle_from(i2)?,
+                Self::swizzle_from(i3)?,
+            ),
+            TexParamKind::WrapS => TexParam::WrapS(Self::wrap_from(i0)?),
+            TexParamKind::WrapT => TexParam::WrapT(Self::wrap_from(i0)?),
+            TexParamKind::WrapR => TexParam::WrapR(Self::wrap_from(i0)?),
+            TexParamKind::BorderColor => TexParam::BorderColor(f0, f1, f2, f3),
+            TexParamKind::BorderColorI => TexParam::BorderColorI(i0, i1, i2, i3),
+            TexParamKind::BorderColorUI => {
+                TexParam::BorderColorUI(i0 as _, i1 as _, i2 as _, i3 as _)
+            }
+            TexParamKind::MaxAnisotropy => TexParam::MaxAnisotropy(f0),
+        })
+    }
+}
+
+impl TexParamKind {
+    /// Return whether this parameter is part of the sampler state,
+    /// texture-only parameters such as the level range and swizzles are not.
+    #[inline]
+    pub const fn is_sampler_state(self) -> bool {
+        !matches!(
+            self,
+            TexParamKind::DepthStencilMode
+                | TexParamKind::BaseLevel
+                | TexParamKind::MaxLevel
+                | TexParamKind::SwizzleR
+                | TexParamKind::SwizzleG
+                | TexParamKind::SwizzleB
+                | TexParamKind::SwizzleA
+                | TexParamKind::SwizzleRGBA
+        )
+    }
+
+    #[inline]
+    pub(super) const fn to_gl_pname(self) -> GLenum {
+        match self {
+            TexParamKind::DepthStencilMode => gl::DEPTH_STENCIL_TEXTURE_MODE,
+            TexParamKind::BaseLevel => gl::TEXTURE_BASE_LEVEL,
+            TexParamKind::CompareFunc => gl::TEXTURE_COMPARE_FUNC,
+            TexParamKind::CompareMode => gl::TEXTURE_COMPARE_MODE,
+            TexParamKind::LodBias => gl::TEXTURE_LOD_BIAS,
+            TexParamKind::MinFilter => gl::TEXTURE_MIN_FILTER,
+            TexParamKind::MagFilter => gl::TEXTURE_MAG_FILTER,
+            TexParamKind::MinLod => gl::TEXTURE_MIN_LOD,
+            TexParamKind::MaxLod => gl::TEXTURE_MAX_LOD,
+            TexParamKind::MaxLevel => gl::TEXTURE_MAX_LEVEL,
+            TexParamKind::SwizzleR => gl::TEXTURE_SWIZZLE_R,
+            TexParamKind::SwizzleG => gl::TEXTURE_SWIZZLE_G,
+            TexParamKind::SwizzleB => gl::TEXTURE_SWIZZLE_B,
+            TexParamKind::SwizzleA => gl::TEXTURE_SWIZZLE_A,
+            TexParamKind::SwizzleRGBA => gl::TEXTURE_SWIZZLE_RGBA,
+            TexParamKind::WrapS => gl::TEXTURE_WRAP_S,
+            TexParamKind::WrapT => gl::TEXTURE_WRAP_T,
+            TexParamKind::WrapR => gl::TEXTURE_WRAP_R,
+            TexParamKind::BorderColor
+            | TexParamKind::BorderColorI
+            | TexParamKind::BorderColorUI => gl::TEXTURE_BORDER_COLOR,
+            TexParamKind::MaxAnisotropy => TEXTURE_MAX_ANISOTROPY,
         }
     }
+
+    /// Return whether the value is queried by `glGet*Parameterfv(...)`.
+    #[inline]
+    pub(super) const fn is_float(self) -> bool {
+        matches!(
+            self,
+            TexParamKind::LodBias
+                | TexParamKind::MinLod
+                | TexParamKind::MaxLod
+                | TexParamKind::BorderColor
+                | TexParamKind::MaxAnisotropy
+        )
+    }
 }
 
 impl TexTarget {
+    /// Return whether a texture of this target has layers, which are faces for cube maps.
+    #[inline]
+    pub const fn is_layered(self) -> bool {
+        matches!(
+            self,
+            TexTarget::Tex3D
+                | TexTarget::Tex1DArray
+                | TexTarget::Tex2DArray
+                | TexTarget::TexCubeMap
+                | TexTarget::TexCubeMapArray
+                | TexTarget::Tex2DMultisampleArray
+        )
+    }
+
     #[inline]
     pub(super) const fn to_gl_target(&self) -> u32 {
         match self {
@@ -477,7 +785,7 @@ impl TexTarget {
             TexTarget::Tex2D => gl::TEXTURE_2D,
             TexTarget::Tex3D => gl::TEXTURE_3D,
             TexTarget::Tex1DArray => gl::TEXTURE_1D_ARRAY,
-            TexTarget::Tex2DArray => gl::TEXTURE_1D_ARRAY,
+            TexTarget::Tex2DArray => gl::TEXTURE_2D_ARRAY,
             TexTarget::TexRectangle => gl::TEXTURE_RECTANGLE,
             TexTarget::TexCubeMap => gl::TEXTURE_CUBE_MAP,
             TexTarget::TexCubeMapArray => gl::TEXTURE_CUBE_MAP_ARRAY,
@@ -486,6 +794,24 @@ impl TexTarget {
             TexTarget::Tex2DMultisampleArray => gl::TEXTURE_2D_MULTISAMPLE_ARRAY,
         }
     }
+
+    #[inline]
+    pub(super) const fn from_gl_target(target: GLenum) -> Option<Self> {
+        match target {
+            gl::TEXTURE_1D => Some(TexTarget::Tex1D),
+            gl::TEXTURE_2D => Some(TexTarget::Tex2D),
+            gl::TEXTURE_3D => Some(TexTarget::Tex3D),
+            gl::TEXTURE_1D_ARRAY => Some(TexTarget::Tex1DArray),
+            gl::TEXTURE_2D_ARRAY => Some(TexTarget::Tex2DArray),
+            gl::TEXTURE_RECTANGLE => Some(TexTarget::TexRectangle),
+            gl::TEXTURE_CUBE_MAP => Some(TexTarget::TexCubeMap),
+            gl::TEXTURE_CUBE_MAP_ARRAY => Some(TexTarget::TexCubeMapArray),
+            gl::TEXTURE_BUFFER => Some(TexTarget::TexBuffer),
+            gl::TEXTURE_2D_MULTISAMPLE => Some(TexTarget::Tex2DMultisample),
+            gl::TEXTURE_2D_MULTISAMPLE_ARRAY => Some(TexTarget::Tex2DMultisampleArray),
+            _ => None,
+        }
+    }
 }
 
 impl MinmapTarget {
@@ -497,7 +823,7 @@ impl MinmapTarget {
             MinmapTarget::Tex2D => gl::TEXTURE_2D,
             MinmapTarget::Tex3D => gl::TEXTURE_3D,
             MinmapTarget::Tex1DArray => gl::TEXTURE_1D_ARRAY,
-            MinmapTarget::Tex2DArray => gl::TEXTURE_1D_ARRAY,
+            MinmapTarget::Tex2DArray => gl::TEXTURE_2D_ARRAY,
             MinmapTarget::TexCubeMap => gl::TEXTURE_CUBE_MAP,
             MinmapTarget::TexCubeMapArray => gl::TEXTURE_CUBE_MAP_ARRAY,
         }
@@ -604,6 +930,25 @@ impl Cap {
     }
 }
 
+/// Capability which can be set per draw buffer or per viewport by `Context::enablei(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum IndexedCap {
+    /// Blending of a draw buffer.
+    Blend,
+    /// Scissor test of a viewport.
+    ScissorTest,
+}
+
+impl IndexedCap {
+    #[inline]
+    pub(crate) const fn to_gl_cap(self) -> GLenum {
+        match self {
+            IndexedCap::Blend => gl::BLEND,
+            IndexedCap::ScissorTest => gl::SCISSOR_TEST,
+        }
+    }
+}
+
 impl ShaderType {
     pub(crate) const fn to_gl_type(self) -> GLenum {
         match self {
@@ -650,9 +995,86 @@ impl BaseFormat {
             BaseFormat::DepthStencil => gl::DEPTH_STENCIL,
         }
     }
+
+    #[inline]
+    pub(super) const fn from_gl_format(format: GLenum) -> Option<Self> {
+        match format {
+            gl::RED => Some(BaseFormat::Red),
+            gl::RG => Some(BaseFormat::RG),
+            gl::RGB => Some(BaseFormat::RGB),
+            gl::RGBA => Some(BaseFormat::RGBA),
+            gl::DEPTH_COMPONENT => Some(BaseFormat::DepthComponent),
+            gl::DEPTH_STENCIL => Some(BaseFormat::DepthStencil),
+            _ => None,
+        }
+    }
 }
 
 impl SizedFormat {
+    /// Return the nominal byte size of one texel, drivers may pad it.
+    #[inline]
+    pub const fn texel_bytes(self) -> usize {
+        match self {
+            SizedFormat::R8
+            | SizedFormat::R8_SNORM
+            | SizedFormat::R3_G3_B2
+            | SizedFormat::RGBA2
+            | SizedFormat::R8I
+            | SizedFormat::R8UI => 1,
+            SizedFormat::R16
+            | SizedFormat::R16_SNORM
+            | SizedFormat::RG8
+            | SizedFormat::RG8_SNORM
+            | SizedFormat::RGB4
+            | SizedFormat::RGB5
+            | SizedFormat::RGBA4
+            | SizedFormat::RGB5_A1
+            | SizedFormat::R16F
+            | SizedFormat::R16I
+            | SizedFormat::R16UI
+            | SizedFormat::RG8I
+            | SizedFormat::RG8UI => 2,
+            SizedFormat::RGB8
+            | SizedFormat::RGB8_SNORM
+            | SizedFormat::SRGB8
+            | SizedFormat::RGB8I
+            | SizedFormat::RGB8UI => 3,
+            SizedFormat::RG16
+            | SizedFormat::RG16_SNORM
+            | SizedFormat::RGBA8
+            | SizedFormat::RGBA8_SNORM
+            | SizedFormat::RGB10
+            | SizedFormat::RGB10_A2
+            | SizedFormat::RGB10_A2UI
+            | SizedFormat::SRGB8_ALPHA8
+            | SizedFormat::RG16F
+            | SizedFormat::R32F
+            | SizedFormat::R11F_G11F_B10F
+            | SizedFormat::RGB9_E5
+            | SizedFormat::R32I
+            | SizedFormat::R32UI
+            | SizedFormat::RG16I
+            | SizedFormat::RG16UI
+            | SizedFormat::RGBA8I
+            | SizedFormat::RGBA8UI => 4,
+            SizedFormat::RGB12
+            | SizedFormat::RGB16_SNORM
+            | SizedFormat::RGBA12
+            | SizedFormat::RGB16F
+            | SizedFormat::RGB16I
+            | SizedFormat::RGB16UI => 6,
+            SizedFormat::RGBA16
+            | SizedFormat::RGBA16F
+            | SizedFormat::RG32F
+            | SizedFormat::RG32I
+            | SizedFormat::RG32UI
+            | SizedFormat::RGBA16I
+            | SizedFormat::RGBA16UI => 8,
+            SizedFormat::RGB32F | SizedFormat::RGB32I | SizedFormat::RGB32UI => 12,
+            SizedFormat::RGBA32F | SizedFormat::RGBA32I | SizedFormat::RGBA32UI => 16,
+        }
+    }
+
     #[inline]
     pub(super) const fn to_gl_format(self) -> GLenum {
         match self {
@@ -719,6 +1141,161 @@ impl SizedFormat {
             SizedFormat::RGBA32UI => gl::RGBA32UI,
         }
     }
+
+    /// Return whether this format can be used by buffer textures.
+    #[inline]
+    pub const fn is_buffer_compatible(self) -> bool {
+        matches!(
+            self,
+            SizedFormat::R8
+                | SizedFormat::R16
+                | SizedFormat::R16F
+                | SizedFormat::R32F
+                | SizedFormat::R8I
+                | SizedFormat::R16I
+                | SizedFormat::R32I
+                | SizedFormat::R8UI
+                | SizedFormat::R16UI
+                | SizedFormat::R32UI
+                | SizedFormat::RG8
+                | SizedFormat::RG16
+                | SizedFormat::RG16F
+                | SizedFormat::RG32F
+                | SizedFormat::RG8I
+                | SizedFormat::RG16I
+                | SizedFormat::RG32I
+                | SizedFormat::RG8UI
+                | SizedFormat::RG16UI
+                | SizedFormat::RG32UI
+                | SizedFormat::RGB32F
+                | SizedFormat::RGB32I
+                | SizedFormat::RGB32UI
+                | SizedFormat::RGBA8
+                | SizedFormat::RGBA16
+                | SizedFormat::RGBA16F
+                | SizedFormat::RGBA32F
+                | SizedFormat::RGBA8I
+                | SizedFormat::RGBA16I
+                | SizedFormat::RGBA32I
+                | SizedFormat::RGBA8UI
+                | SizedFormat::RGBA16UI
+                | SizedFormat::RGBA32UI
+        )
+    }
+
+    /// Return whether this format can be used by image load/store operations.
+    #[inline]
+    pub const fn is_image_compatible(self) -> bool {
+        matches!(
+            self,
+            SizedFormat::RGBA32F
+                | SizedFormat::RGBA16F
+                | SizedFormat::RG32F
+                | SizedFormat::RG16F
+                | SizedFormat::R11F_G11F_B10F
+                | SizedFormat::R32F
+                | SizedFormat::R16F
+                | SizedFormat::RGBA32UI
+                | SizedFormat::RGBA16UI
+                | SizedFormat::RGB10_A2UI
+                | SizedFormat::RGBA8UI
+                | SizedFormat::RG32UI
+                | SizedFormat::RG16UI
+                | SizedFormat::RG8UI
+                | SizedFormat::R32UI
+                | SizedFormat::R16UI
+                | SizedFormat::R8UI
+                | SizedFormat::RGBA32I
+                | SizedFormat::RGBA16I
+                | SizedFormat::RGBA8I
+                | SizedFormat::RG32I
+                | SizedFormat::RG16I
+                | SizedFormat::RG8I
+                | SizedFormat::R32I
+                | SizedFormat::R16I
+                | SizedFormat::R8I
+                | SizedFormat::RGBA16
+                | SizedFormat::RGB10_A2
+                | SizedFormat::RGBA8
+                | SizedFormat::RG16
+                | SizedFormat::RG8
+                | SizedFormat::R16
+                | SizedFormat::R8
+                | SizedFormat::RGBA8_SNORM
+                | SizedFormat::RG16_SNORM
+                | SizedFormat::RG8_SNORM
+                | SizedFormat::R16_SNORM
+                | SizedFormat::R8_SNORM
+        )
+    }
+
+    #[inline]
+    pub(super) const fn from_gl_format(format: GLenum) -> Option<Self> {
+        match format {
+            gl::R8 => Some(SizedFormat::R8),
+            gl::R8_SNORM => Some(SizedFormat::R8_SNORM),
+            gl::R16 => Some(SizedFormat::R16),
+            gl::R16_SNORM => Some(SizedFormat::R16_SNORM),
+            gl::RG8 => Some(SizedFormat::RG8),
+            gl::RG8_SNORM => Some(SizedFormat::RG8_SNORM),
+            gl::RG16 => Some(SizedFormat::RG16),
+            gl::RG16_SNORM => Some(SizedFormat::RG16_SNORM),
+            gl::R3_G3_B2 => Some(SizedFormat::R3_G3_B2),
+            gl::RGB4 => Some(SizedFormat::RGB4),
+            gl::RGB5 => Some(SizedFormat::RGB5),
+            gl::RGB8 => Some(SizedFormat::RGB8),
+            gl::RGB8_SNORM => Some(SizedFormat::RGB8_SNORM),
+            gl::RGB10 => Some(SizedFormat::RGB10),
+            gl::RGB12 => Some(SizedFormat::RGB12),
+            gl::RGB16_SNORM => Some(SizedFormat::RGB16_SNORM),
+            gl::RGBA2 => Some(SizedFormat::RGBA2),
+            gl::RGBA4 => Some(SizedFormat::RGBA4),
+            gl::RGB5_A1 => Some(SizedFormat::RGB5_A1),
+            gl::RGBA8 => Some(SizedFormat::RGBA8),
+            gl::RGBA8_SNORM => Some(SizedFormat::RGBA8_SNORM),
+            gl::RGB10_A2 => Some(SizedFormat::RGB10_A2),
+            gl::RGB10_A2UI => Some(SizedFormat::RGB10_A2UI),
+            gl::RGBA12 => Some(SizedFormat::RGBA12),
+            gl::RGBA16 => Some(SizedFormat::RGBA16),
+            gl::SRGB8 => Some(SizedFormat::SRGB8),
+            gl::SRGB8_ALPHA8 => Some(SizedFormat::SRGB8_ALPHA8),
+            gl::R16F => Some(SizedFormat::R16F),
+            gl::RG16F => Some(SizedFormat::RG16F),
+            gl::RGB16F => Some(SizedFormat::RGB16F),
+            gl::RGBA16F => Some(SizedFormat::RGBA16F),
+            gl::R32F => Some(SizedFormat::R32F),
+            gl::RG32F => Some(SizedFormat::RG32F),
+            gl::RGB32F => Some(SizedFormat::RGB32F),
+            gl::RGBA32F => Some(SizedFormat::RGBA32F),
+            gl::R11F_G11F_B10F => Some(SizedFormat::R11F_G11F_B10F),
+            gl::RGB9_E5 => Some(SizedFormat::RGB9_E5),
+            gl::R8I => Some(SizedFormat::R8I),
+            gl::R8UI => Some(SizedFormat::R8UI),
+            gl::R16I => Some(SizedFormat::R16I),
+            gl::R16UI => Some(SizedFormat::R16UI),
+            gl::R32I => Some(SizedFormat::R32I),
+            gl::R32UI => Some(SizedFormat::R32UI),
+            gl::RG8I => Some(SizedFormat::RG8I),
+            gl::RG8UI => Some(SizedFormat::RG8UI),
+            gl::RG16I => Some(SizedFormat::RG16I),
+            gl::RG16UI => Some(SizedFormat::RG16UI),
+            gl::RG32I => Some(SizedFormat::RG32I),
+            gl::RG32UI => Some(SizedFormat::RG32UI),
+            gl::RGB8I => Some(SizedFormat::RGB8I),
+            gl::RGB8UI => Some(SizedFormat::RGB8UI),
+            gl::RGB16I => Some(SizedFormat::RGB16I),
+            gl::RGB16UI => Some(SizedFormat::RGB16UI),
+            gl::RGB32I => Some(SizedFormat::RGB32I),
+            gl::RGB32UI => Some(SizedFormat::RGB32UI),
+            gl::RGBA8I => Some(SizedFormat::RGBA8I),
+            gl::RGBA8UI => Some(SizedFormat::RGBA8UI),
+            gl::RGBA16I => Some(SizedFormat::RGBA16I),
+            gl::RGBA16UI => Some(SizedFormat::RGBA16UI),
+            gl::RGBA32I => Some(SizedFormat::RGBA32I),
+            gl::RGBA32UI => Some(SizedFormat::RGBA32UI),
+            _ => None,
+        }
+    }
 }
 
 impl CompressedFormat {
@@ -739,6 +1316,92 @@ impl CompressedFormat {
             CompressedFormat::SRGB_ALPHA_BPTC_UNORM => gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM,
             CompressedFormat::RGB_BPTC_SIGNED_FLOAT => gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT,
             CompressedFormat::RGB_BPTC_UNSIGNED_FLOAT => gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT,
+            CompressedFormat::RGB_S3TC_DXT1 => COMPRESSED_RGB_S3TC_DXT1_EXT,
+            CompressedFormat::RGBA_S3TC_DXT1 => COMPRESSED_RGBA_S3TC_DXT1_EXT,
+            CompressedFormat::RGBA_S3TC_DXT3 => COMPRESSED_RGBA_S3TC_DXT3_EXT,
+            CompressedFormat::RGBA_S3TC_DXT5 => COMPRESSED_RGBA_S3TC_DXT5_EXT,
+            CompressedFormat::SRGB_S3TC_DXT1 => COMPRESSED_SRGB_S3TC_DXT1_EXT,
+            CompressedFormat::SRGB_ALPHA_S3TC_DXT1 => COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT,
+            CompressedFormat::SRGB_ALPHA_S3TC_DXT3 => COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT,
+            CompressedFormat::SRGB_ALPHA_S3TC_DXT5 => COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT,
+        }
+    }
+
+    #[inline]
+    pub(super) const fn from_gl_format(format: GLenum) -> Option<Self> {
+        match format {
+            gl::COMPRESSED_RED => Some(CompressedFormat::RED),
+            gl::COMPRESSED_RG => Some(CompressedFormat::RG),
+            gl::COMPRESSED_RGB => Some(CompressedFormat::RGB),
+            gl::COMPRESSED_RGBA => Some(CompressedFormat::RGBA),
+            gl::COMPRESSED_SRGB => Some(CompressedFormat::SRGB),
+            gl::COMPRESSED_SRGB_ALPHA => Some(CompressedFormat::SRGB_ALPHA),
+            gl::COMPRESSED_RED_RGTC1 => Some(CompressedFormat::RED_RGTC1),
+            gl::COMPRESSED_SIGNED_RED_RGTC1 => Some(CompressedFormat::SIGNED_RED_RGTC1),
+            gl::COMPRESSED_RG_RGTC2 => Some(CompressedFormat::RG_RGTC2),
+            gl::COMPRESSED_SIGNED_RG_RGTC2 => Some(CompressedFormat::SIGNED_RG_RGTC2),
+            gl::COMPRESSED_RGBA_BPTC_UNORM => Some(CompressedFormat::RGBA_BPTC_UNORM),
+            gl::COMPRESSED_SRGB_ALPHA_BPTC_UNORM => Some(CompressedFormat::SRGB_ALPHA_BPTC_UNORM),
+            gl::COMPRESSED_RGB_BPTC_SIGNED_FLOAT => Some(CompressedFormat::RGB_BPTC_SIGNED_FLOAT),
+            gl::COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => {
+                Some(CompressedFormat::RGB_BPTC_UNSIGNED_FLOAT)
+            }
+            COMPRESSED_RGB_S3TC_DXT1_EXT => Some(CompressedFormat::RGB_S3TC_DXT1),
+            COMPRESSED_RGBA_S3TC_DXT1_EXT => Some(CompressedFormat::RGBA_S3TC_DXT1),
+            COMPRESSED_RGBA_S3TC_DXT3_EXT => Some(CompressedFormat::RGBA_S3TC_DXT3),
+            COMPRESSED_RGBA_S3TC_DXT5_EXT => Some(CompressedFormat::RGBA_S3TC_DXT5),
+            COMPRESSED_SRGB_S3TC_DXT1_EXT => Some(CompressedFormat::SRGB_S3TC_DXT1),
+            COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT => Some(CompressedFormat::SRGB_ALPHA_S3TC_DXT1),
+            COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT => Some(CompressedFormat::SRGB_ALPHA_S3TC_DXT3),
+            COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT => Some(CompressedFormat::SRGB_ALPHA_S3TC_DXT5),
+            _ => None,
+        }
+    }
+
+    /// BC1 (DXT1) with 1-bit alpha.
+    pub const BC1: Self = Self::RGBA_S3TC_DXT1;
+    /// BC2 (DXT3).
+    pub const BC2: Self = Self::RGBA_S3TC_DXT3;
+    /// BC3 (DXT5).
+    pub const BC3: Self = Self::RGBA_S3TC_DXT5;
+    /// BC4 (RGTC1).
+    pub const BC4: Self = Self::RED_RGTC1;
+    /// BC5 (RGTC2).
+    pub const BC5: Self = Self::RG_RGTC2;
+    /// BC6H (unsigned float BPTC).
+    pub const BC6H: Self = Self::RGB_BPTC_UNSIGNED_FLOAT;
+    /// BC7 (BPTC).
+    pub const BC7: Self = Self::RGBA_BPTC_UNORM;
+
+    /// Return the byte size of one 4x4 block.
+    ///
+    /// The generic formats (`RED`, `RG`, `RGB`, ...) let the driver choose the
+    /// compression, so they have no fixed block size and return `None`.
+    #[inline]
+    pub const fn block_bytes(self) -> Option<usize> {
+        match self {
+            CompressedFormat::RED
+            | CompressedFormat::RG
+            | CompressedFormat::RGB
+            | CompressedFormat::RGBA
+            | CompressedFormat::SRGB
+            | CompressedFormat::SRGB_ALPHA => None,
+            CompressedFormat::RED_RGTC1
+            | CompressedFormat::SIGNED_RED_RGTC1
+            | CompressedFormat::RGB_S3TC_DXT1
+            | CompressedFormat::RGBA_S3TC_DXT1
+            | CompressedFormat::SRGB_S3TC_DXT1
+            | CompressedFormat::SRGB_ALPHA_S3TC_DXT1 => Some(8),
+            _ => Some(16),
+        }
+    }
+
+    /// Return the byte size of a `width` x `height` image in this format.
+    #[inline]
+    pub const fn image_bytes(self, (width, height): (u32, u32)) -> Option<usize> {
+        match self.block_bytes() {
+            Some(bytes) => Some(width.div_ceil(4) as usize * height.div_ceil(4) as usize * bytes),
+            None => None,
         }
     }
 }
@@ -752,9 +1415,83 @@ impl InternalFormat {
             InternalFormat::Compressed(compressed_format) => compressed_format.to_gl_format(),
         }
     }
+
+    #[inline]
+    pub(super) const fn from_gl_format(format: GLenum) -> Option<Self> {
+        if let Some(format) = SizedFormat::from_gl_format(format) {
+            Some(InternalFormat::Sized(format))
+        } else if let Some(format) = CompressedFormat::from_gl_format(format) {
+            Some(InternalFormat::Compressed(format))
+        } else if let Some(format) = BaseFormat::from_gl_format(format) {
+            Some(InternalFormat::Base(format))
+        } else {
+            None
+        }
+    }
 }
 
 impl ImageFormat {
+    /// Return the number of components of this format.
+    #[inline]
+    pub const fn components(self) -> usize {
+        match self {
+            ImageFormat::Red | ImageFormat::RedInteger | ImageFormat::DepthComponent => 1,
+            ImageFormat::RG | ImageFormat::RGInteger | ImageFormat::DepthStencil => 2,
+            ImageFormat::RGB
+            | ImageFormat::BGR
+            | ImageFormat::RGBInteger
+            | ImageFormat::BGRInteger => 3,
+            ImageFormat::RGBA
+            | ImageFormat::BGRA
+            | ImageFormat::RGBAInteger
+            | ImageFormat::BGRAInteger => 4,
+        }
+    }
+
+    /// Return whether the components are unnormalized integers,
+    /// required to transfer pixels of integer textures such as `SizedFormat::R32UI`.
+    #[inline]
+    pub const fn is_integer(self) -> bool {
+        matches!(
+            self,
+            ImageFormat::RedInteger
+                | ImageFormat::RGInteger
+                | ImageFormat::RGBInteger
+                | ImageFormat::BGRInteger
+                | ImageFormat::RGBAInteger
+                | ImageFormat::BGRAInteger
+        )
+    }
+
+    /// Return the base format holding the components of this format.
+    #[inline]
+    pub const fn to_base_format(self) -> BaseFormat {
+        match self {
+            ImageFormat::Red | ImageFormat::RedInteger => BaseFormat::Red,
+            ImageFormat::RG | ImageFormat::RGInteger => BaseFormat::RG,
+            ImageFormat::RGB
+            | ImageFormat::BGR
+            | ImageFormat::RGBInteger
+            | ImageFormat::BGRInteger => BaseFormat::RGB,
+            ImageFormat::RGBA
+            | ImageFormat::BGRA
+            | ImageFormat::RGBAInteger
+            | ImageFormat::BGRAInteger => BaseFormat::RGBA,
+            ImageFormat::DepthComponent => BaseFormat::DepthComponent,
+            ImageFormat::DepthStencil => BaseFormat::DepthStencil,
+        }
+    }
+
+    /// Return the byte size of one pixel of this format stored as `type_`.
+    #[inline]
+    pub const fn pixel_bytes(self, type_: PixelDataType) -> usize {
+        if type_.is_packed() {
+            type_.size()
+        } else {
+            self.components() * type_.size()
+        }
+    }
+
     #[inline]
     pub(super) const fn to_gl_format(self) -> GLenum {
         match self {
@@ -764,6 +1501,12 @@ impl ImageFormat {
             ImageFormat::BGR => gl::BGR,
             ImageFormat::RGBA => gl::RGBA,
             ImageFormat::BGRA => gl::BGRA,
+            ImageFormat::RedInteger => gl::RED_INTEGER,
+            ImageFormat::RGInteger => gl::RG_INTEGER,
+            ImageFormat::RGBInteger => gl::RGB_INTEGER,
+            ImageFormat::BGRInteger => gl::BGR_INTEGER,
+            ImageFormat::RGBAInteger => gl::RGBA_INTEGER,
+            ImageFormat::BGRAInteger => gl::BGRA_INTEGER,
             ImageFormat::DepthComponent => gl::DEPTH_COMPONENT,
             ImageFormat::DepthStencil => gl::DEPTH_STENCIL,
         }
@@ -771,6 +1514,41 @@ impl ImageFormat {
 }
 
 impl PixelDataType {
+    /// Return the byte size of one value of this type.
+    ///
+    /// For packed types, one value holds all components of a pixel.
+    #[inline]
+    pub const fn size(self) -> usize {
+        match self {
+            PixelDataType::u8 | PixelDataType::i8 => 1,
+            PixelDataType::u8_3_3_2 | PixelDataType::u8_2_3_3_REV => 1,
+            PixelDataType::u16 | PixelDataType::i16 | PixelDataType::HalfFloat => 2,
+            PixelDataType::u16_5_6_5
+            | PixelDataType::u16_5_6_5_REV
+            | PixelDataType::u16_4_4_4_4
+            | PixelDataType::u16_4_4_4_4_REV
+            | PixelDataType::u16_5_5_5_1
+            | PixelDataType::u16_1_5_5_5_REV => 2,
+            _ => 4,
+        }
+    }
+
+    /// Return whether all components of a pixel are packed in one value.
+    #[inline]
+    pub const fn is_packed(self) -> bool {
+        !matches!(
+            self,
+            PixelDataType::u8
+                | PixelDataType::i8
+                | PixelDataType::u16
+                | PixelDataType::i16
+                | PixelDataType::u32
+                | PixelDataType::i32
+                | PixelDataType::f32
+                | PixelDataType::HalfFloat
+        )
+    }
+
     #[inline]
     pub(super) const fn to_gl_type(self) -> GLenum {
         match self {
@@ -812,6 +1590,21 @@ impl CompareFunc {
             CompareFunc::Always => gl::ALWAYS,
         }
     }
+
+    #[inline]
+    pub(super) const fn from_gl_func(func: GLenum) -> Option<Self> {
+        match func {
+            gl::NEVER => Some(CompareFunc::Never),
+            gl::LESS => Some(CompareFunc::Less),
+            gl::EQUAL => Some(CompareFunc::Equal),
+            gl::LEQUAL => Some(CompareFunc::LessEqual),
+            gl::GREATER => Some(CompareFunc::Greater),
+            gl::NOTEQUAL => Some(CompareFunc::NotEqual),
+            gl::GEQUAL => Some(CompareFunc::GreaterEqual),
+            gl::ALWAYS => Some(CompareFunc::Always),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -904,6 +1697,24 @@ impl BlendEquation {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PolygonMode {
+    Point,
+    Line,
+    Fill,
+}
+
+impl PolygonMode {
+    #[inline]
+    pub(super) const fn to_gl_mode(self) -> GLenum {
+        match self {
+            PolygonMode::Point => gl::POINT,
+            PolygonMode::Line => gl::LINE,
+            PolygonMode::Fill => gl::FILL,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum CullFace {
     Front,
@@ -938,6 +1749,82 @@ impl FrontFace {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogicOp {
+    Clear,
+    Set,
+    Copy,
+    CopyInverted,
+    Noop,
+    Invert,
+    And,
+    Nand,
+    Or,
+    Nor,
+    Xor,
+    Equiv,
+    AndReverse,
+    AndInverted,
+    OrReverse,
+    OrInverted,
+}
+
+impl LogicOp {
+    #[inline]
+    pub(super) const fn to_gl_op(self) -> GLenum {
+        match self {
+            LogicOp::Clear => gl::CLEAR,
+            LogicOp::Set => gl::SET,
+            LogicOp::Copy => gl::COPY,
+            LogicOp::CopyInverted => gl::COPY_INVERTED,
+            LogicOp::Noop => gl::NOOP,
+            LogicOp::Invert => gl::INVERT,
+            LogicOp::And => gl::AND,
+            LogicOp::Nand => gl::NAND,
+            LogicOp::Or => gl::OR,
+            LogicOp::Nor => gl::NOR,
+            LogicOp::Xor => gl::XOR,
+            LogicOp::Equiv => gl::EQUIV,
+            LogicOp::AndReverse => gl::AND_REVERSE,
+            LogicOp::AndInverted => gl::AND_INVERTED,
+            LogicOp::OrReverse => gl::OR_REVERSE,
+            LogicOp::OrInverted => gl::OR_INVERTED,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClipOrigin {
+    LowerLeft,
+    UpperLeft,
+}
+
+impl ClipOrigin {
+    #[inline]
+    pub(super) const fn to_gl_origin(self) -> GLenum {
+        match self {
+            ClipOrigin::LowerLeft => gl::LOWER_LEFT,
+            ClipOrigin::UpperLeft => gl::UPPER_LEFT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClipDepth {
+    NegativeOneToOne,
+    ZeroToOne,
+}
+
+impl ClipDepth {
+    #[inline]
+    pub(super) const fn to_gl_depth(self) -> GLenum {
+        match self {
+            ClipDepth::NegativeOneToOne => gl::NEGATIVE_ONE_TO_ONE,
+            ClipDepth::ZeroToOne => gl::ZERO_TO_ONE,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FrameBufferTarget {
     Read,
@@ -951,14 +1838,82 @@ impl FrameBufferTarget {
         match self {
             FrameBufferTarget::Read => gl::READ_FRAMEBUFFER,
             FrameBufferTarget::Draw => gl::DRAW_FRAMEBUFFER,
-            FrameBufferTarget::ReadDraw => gl::READ_FRAMEBUFFER,
+            FrameBufferTarget::ReadDraw => gl::FRAMEBUFFER,
+        }
+    }
+}
+
+/// Reason why a frame buffer is not complete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FrameBufferError {
+    /// The default frame buffer does not exist.
+    Undefined,
+    /// An attachment is not attachment complete, e.g. it has no storage.
+    IncompleteAttachment,
+    /// No image is attached.
+    MissingAttachment,
+    /// A draw buffer names an attachment without an image.
+    IncompleteDrawBuffer,
+    /// The read buffer names an attachment without an image.
+    IncompleteReadBuffer,
+    /// The combination of internal formats is not supported by the implementation.
+    Unsupported,
+    /// The attachments have different numbers of samples or sample locations.
+    IncompleteMultisample,
+    /// Layered and non-layered images, or images of different targets are mixed.
+    IncompleteLayerTargets,
+    /// Unknown status code.
+    Unknown(u32),
+}
+
+impl FrameBufferError {
+    /// Return `None` for `GL_FRAMEBUFFER_COMPLETE`.
+    #[inline]
+    pub(super) const fn from_gl_status(status: GLenum) -> Option<Self> {
+        Some(match status {
+            gl::FRAMEBUFFER_COMPLETE => return None,
+            gl::FRAMEBUFFER_UNDEFINED => FrameBufferError::Undefined,
+            gl::FRAMEBUFFER_INCOMPLETE_ATTACHMENT => FrameBufferError::IncompleteAttachment,
+            gl::FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT => FrameBufferError::MissingAttachment,
+            gl::FRAMEBUFFER_INCOMPLETE_DRAW_BUFFER => FrameBufferError::IncompleteDrawBuffer,
+            gl::FRAMEBUFFER_INCOMPLETE_READ_BUFFER => FrameBufferError::IncompleteReadBuffer,
+            gl::FRAMEBUFFER_UNSUPPORTED => FrameBufferError::Unsupported,
+            gl::FRAMEBUFFER_INCOMPLETE_MULTISAMPLE => FrameBufferError::IncompleteMultisample,
+            gl::FRAMEBUFFER_INCOMPLETE_LAYER_TARGETS => FrameBufferError::IncompleteLayerTargets,
+            _ => FrameBufferError::Unknown(status),
+        })
+    }
+}
+
+impl std::fmt::Display for FrameBufferError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameBufferError::Undefined => write!(f, "Frame buffer is undefined"),
+            FrameBufferError::IncompleteAttachment => write!(f, "Incomplete attachment"),
+            FrameBufferError::MissingAttachment => write!(f, "Missing attachment"),
+            FrameBufferError::IncompleteDrawBuffer => write!(f, "Incomplete draw buffer"),
+            FrameBufferError::IncompleteReadBuffer => write!(f, "Incomplete read buffer"),
+            FrameBufferError::Unsupported => write!(f, "Unsupported combination of formats"),
+            FrameBufferError::IncompleteMultisample => write!(f, "Incomplete multisample"),
+            FrameBufferError::IncompleteLayerTargets => write!(f, "Incomplete layer targets"),
+            FrameBufferError::Unknown(status) => write!(f, "Not complete, error code: {}", status),
         }
     }
 }
 
+impl std::error::Error for FrameBufferError {}
+
+impl From<FrameBufferError> for String {
+    #[inline]
+    fn from(err: FrameBufferError) -> Self {
+        err.to_string()
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum FrameBufferAttachment {
-    Color,
+    /// Color attachment `n`, which must be less than `GL_MAX_COLOR_ATTACHMENTS`.
+    Color(u32),
     Depth,
     Stencil,
     DepthStencil,
@@ -968,7 +1923,7 @@ impl FrameBufferAttachment {
     #[inline]
     pub(super) const fn to_gl_attachment(self) -> GLenum {
         match self {
-            FrameBufferAttachment::Color => gl::COLOR_ATTACHMENT0,
+            FrameBufferAttachment::Color(index) => gl::COLOR_ATTACHMENT0 + index,
             FrameBufferAttachment::Depth => gl::DEPTH_ATTACHMENT,
             FrameBufferAttachment::Stencil => gl::STENCIL_ATTACHMENT,
             FrameBufferAttachment::DepthStencil => gl::DEPTH_STENCIL_ATTACHMENT,
@@ -976,6 +1931,33 @@ impl FrameBufferAttachment {
     }
 }
 
+/// Color buffer of the default frame buffer, see `DefaultFrameBuffer::draw_buffer(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DefaultBuffer {
+    Front,
+    Back,
+    FrontLeft,
+    FrontRight,
+    BackLeft,
+    BackRight,
+    FrontAndBack,
+}
+
+impl DefaultBuffer {
+    #[inline]
+    pub(super) const fn to_gl_buffer(self) -> GLenum {
+        match self {
+            DefaultBuffer::Front => gl::FRONT,
+            DefaultBuffer::Back => gl::BACK,
+            DefaultBuffer::FrontLeft => gl::FRONT_LEFT,
+            DefaultBuffer::FrontRight => gl::FRONT_RIGHT,
+            DefaultBuffer::BackLeft => gl::BACK_LEFT,
+            DefaultBuffer::BackRight => gl::BACK_RIGHT,
+            DefaultBuffer::FrontAndBack => gl::FRONT_AND_BACK,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum TextureTarget {
     Tex2d,
@@ -1044,6 +2026,21 @@ pub enum RenderBufferFormat {
 }
 
 impl RenderBufferFormat {
+    /// Return the attachment point of a depth or stencil format, `None` for color formats.
+    #[inline]
+    pub const fn depth_stencil_attachment(self) -> Option<FrameBufferAttachment> {
+        match self {
+            RenderBufferFormat::DEPTH_COMPONENT16
+            | RenderBufferFormat::DEPTH_COMPONENT24
+            | RenderBufferFormat::DEPTH_COMPONENT32F => Some(FrameBufferAttachment::Depth),
+            RenderBufferFormat::DEPTH24_STENCIL8 | RenderBufferFormat::DEPTH32F_STENCIL8 => {
+                Some(FrameBufferAttachment::DepthStencil)
+            }
+            RenderBufferFormat::STENCIL_INDEX => Some(FrameBufferAttachment::Stencil),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub(super) const fn to_gl_format(self) -> GLenum {
         match self {
@@ -1085,25 +2082,6 @@ impl RenderBufferFormat {
     }
 }
 
-pub enum Attachmect {
-    Color(u32),
-    Depth,
-    Stencil,
-    DepthStencil,
-}
-
-impl Attachmect {
-    #[inline]
-    pub(super) const fn to_gl_attachment(self) -> GLenum {
-        match self {
-            Attachmect::Color(index) => gl::COLOR_ATTACHMENT0 + index,
-            Attachmect::Depth => gl::DEPTH_ATTACHMENT,
-            Attachmect::Stencil => gl::STENCIL_ATTACHMENT,
-            Attachmect::DepthStencil => gl::DEPTH_STENCIL_ATTACHMENT,
-        }
-    }
-}
-
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum GlType {
@@ -1189,3 +2167,60 @@ impl Filter {
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    #[inline]
+    pub(super) const fn to_gl_access(self) -> GLenum {
+        match self {
+            Access::Read => gl::READ_ONLY,
+            Access::Write => gl::WRITE_ONLY,
+            Access::ReadWrite => gl::READ_WRITE,
+        }
+    }
+}
+
+/// Layers of an array, cube map or 3D texture bound as an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImageLayer {
+    /// Bind all layers.
+    Layered,
+    /// Bind a single layer.
+    Layer(u32),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_block_size() {
+        assert_eq!(CompressedFormat::BC1.block_bytes(), Some(8));
+        assert_eq!(CompressedFormat::BC3.block_bytes(), Some(16));
+        assert_eq!(CompressedFormat::BC7.image_bytes((5, 4)), Some(32));
+        assert_eq!(CompressedFormat::RGBA.image_bytes((4, 4)), None);
+    }
+
+    #[test]
+    fn test_clear_mask() {
+        let mask = ClearMask::COLOR | ClearMask::DEPTH;
+        assert!(mask.contains(ClearMask::COLOR));
+        assert!(!mask.contains(ClearMask::STENCIL));
+        assert!(ClearMask::ALL.contains(mask));
+        assert_eq!(Mask::COLOR_BUFFER_BIT, ClearMask::COLOR);
+        assert!(ClearMask::default().is_empty());
+
+        let values = ClearValues {
+            depth: Some(0.0),
+            stencil: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(values.mask(), ClearMask::DEPTH | ClearMask::STENCIL);
+    }
+}
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use gl::types::GLsync;
+
+/// Sync object signaled when the GPU reaches it in the command stream.
+pub struct Fence {
+    sync: GLsync,
+}
+
+impl Fence {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let sync = unsafe { gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0) };
+        Self { sync }
+    }
+
+    /// Return whether all commands before the fence have completed, without blocking.
+    #[inline]
+    pub fn is_signaled(&self) -> bool {
+        let mut status = 0;
+        unsafe {
+            gl::GetSynciv(
+                self.sync,
+                gl::SYNC_STATUS,
+                1,
+                std::ptr::null_mut(),
+                &mut status,
+            );
+        }
+        status as u32 == gl::SIGNALED
+    }
+
+    /// Wrapper of `glClientWaitSync(...)`
+    ///
+    /// Block until the fence is signaled or `timeout` has elapsed, return whether it is signaled.
+    #[inline]
+    pub fn wait(&self, timeout: Duration) -> bool {
+        let timeout = u64::try_from(timeout.as_nanos()).unwrap_or(u64::MAX);
+        let status = unsafe { gl::ClientWaitSync(self.sync, gl::SYNC_FLUSH_COMMANDS_BIT, timeout) };
+        status == gl::ALREADY_SIGNALED || status == gl::CONDITION_SATISFIED
+    }
+}
+
+impl Drop for Fence {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteSync(self.sync);
+        }
+    }
+}
@@ -1,6 +1,14 @@
-use gl::types::GLuint;
+use std::marker::PhantomData;
 
-use super::{Attachmect, Filter, FrameBufferTarget, RenderBuffer};
+use gl::types::{GLenum, GLuint};
+
+use super::{
+    check_attachment, check_error,
+    state::{self, Slot},
+    Buffer, ClearMask, DefaultBuffer, Fence, Filter, FrameBufferAttachment, FrameBufferError,
+    FrameBufferTarget, ImageFormat, PixelDataType, PlainData, RenderBuffer, TexTarget, Texture,
+    Usage,
+};
 
 pub struct FrameBuffer {
     fbo: GLuint,
@@ -11,18 +19,186 @@ impl FrameBuffer {
     pub(super) fn new() -> Self {
         let mut fbo = 0;
         unsafe {
-            gl::GenFramebuffers(1, &mut fbo);
+            gl::CreateFramebuffers(1, &mut fbo);
         }
         Self { fbo }
     }
 }
 
+/// The frame buffer of the window surface, whose name is `0`.
+///
+/// It only exposes the operations valid on it, its buffers are selected by `DefaultBuffer`
+/// and named by `FrameBufferAttachment::Color(0)`, `Depth` and `Stencil` when invalidated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DefaultFrameBuffer;
+
+/// Name `0` used by `DefaultFrameBuffer` to share the implementation of `FrameBuffer`.
+static DEFAULT_FRAME_BUFFER: FrameBuffer = FrameBuffer { fbo: 0 };
+
 impl Drop for FrameBuffer {
     #[inline]
     fn drop(&mut self) {
         unsafe {
             gl::DeleteFramebuffers(1, &self.fbo);
         }
+        state::forget_name(self.fbo, |slot| matches!(slot, Slot::FrameBuffer(_)));
+    }
+}
+
+/// Guard of `FrameBuffer::bind_scoped(...)`, restoring the previous bindings on drop.
+pub struct FrameBufferBinding<'a> {
+    read: GLuint,
+    draw: GLuint,
+    viewport: [i32; 4],
+    _frame_buffer: PhantomData<&'a FrameBuffer>,
+}
+
+impl Drop for FrameBufferBinding<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        let [x, y, width, height] = self.viewport;
+        unsafe {
+            gl::BindFramebuffer(gl::READ_FRAMEBUFFER, self.read);
+            gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, self.draw);
+            gl::Viewport(x, y, width, height);
+        }
+        state::set(Slot::FrameBuffer(gl::READ_FRAMEBUFFER), &[self.read]);
+        state::set(Slot::FrameBuffer(gl::DRAW_FRAMEBUFFER), &[self.draw]);
+    }
+}
+
+/// Binding of `Target::PixelPack` for a read, restoring the previous buffer on drop.
+struct PixelPackBinding {
+    previous: GLuint,
+}
+
+impl PixelPackBinding {
+    #[inline]
+    fn new(buffer: GLuint) -> Self {
+        let mut previous = 0;
+        unsafe {
+            gl::GetIntegerv(gl::PIXEL_PACK_BUFFER_BINDING, &mut previous);
+            gl::BindBuffer(gl::PIXEL_PACK_BUFFER, buffer);
+        }
+        state::set(Slot::Buffer(gl::PIXEL_PACK_BUFFER), &[buffer]);
+        Self {
+            previous: previous as _,
+        }
+    }
+}
+
+impl Drop for PixelPackBinding {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            gl::BindBuffer(gl::PIXEL_PACK_BUFFER, self.previous);
+        }
+        state::set(Slot::Buffer(gl::PIXEL_PACK_BUFFER), &[self.previous]);
+    }
+}
+
+/// Pixels being read into a buffer by `FrameBuffer::read_pixels_async(...)`.
+pub struct PixelTransfer {
+    buffer: Buffer,
+    fence: Fence,
+    size: (u32, u32),
+    format: ImageFormat,
+    type_: PixelDataType,
+}
+
+impl PixelTransfer {
+    /// Return whether the pixels have arrived in the buffer.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        self.fence.is_signaled()
+    }
+
+    /// Block until the pixels have arrived or `timeout` has elapsed, return whether they arrived.
+    #[inline]
+    pub fn wait(&self, timeout: std::time::Duration) -> bool {
+        self.fence.wait(timeout)
+    }
+
+    /// Wait for the pixels and download them, rows are tightly packed from the bottom up.
+    #[allow(private_bounds)]
+    pub fn read<T: PlainData>(&self) -> Result<Vec<T>, String> {
+        let bytes = pixels_bytes::<T>(self.size, self.format, self.type_)?;
+        let mut pixels = vec![T::ZERO; bytes / std::mem::size_of::<T>()];
+        self.fence.wait(std::time::Duration::MAX);
+        self.buffer.get_sub_data(0, &mut pixels);
+        check_error("Pixel Transfer Error")?;
+        Ok(pixels)
+    }
+
+    /// Return the buffer receiving the pixels.
+    #[inline]
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+
+    /// Return the size of the read rectangle.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the pixel format of the read pixels.
+    #[inline]
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Return the pixel data type of the read pixels.
+    #[inline]
+    pub fn type_(&self) -> PixelDataType {
+        self.type_
+    }
+}
+
+/// Return the byte size of tightly packed pixels, checking it is a multiple of the size of `T`.
+fn pixels_bytes<T>(
+    (width, height): (u32, u32),
+    format: ImageFormat,
+    type_: PixelDataType,
+) -> Result<usize, String> {
+    if std::mem::size_of::<T>() == 0 {
+        return Err("Pixels can't be read into zero-sized values".to_string());
+    }
+    let bytes = width as usize * height as usize * format.pixel_bytes(type_);
+    if !bytes.is_multiple_of(std::mem::size_of::<T>()) {
+        return Err(format!(
+            "{} bytes of pixels are not a multiple of {} bytes",
+            bytes,
+            std::mem::size_of::<T>()
+        ));
+    }
+    Ok(bytes)
+}
+
+#[inline]
+fn read_pixels(
+    ((x, y), (width, height)): ((u32, u32), (u32, u32)),
+    format: ImageFormat,
+    type_: PixelDataType,
+    bytes: usize,
+    data: *mut std::ffi::c_void,
+) {
+    // Rows are tightly packed, the alignment of the application is restored afterwards.
+    let mut alignment = 0;
+    unsafe {
+        gl::GetIntegerv(gl::PACK_ALIGNMENT, &mut alignment);
+        gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+        gl::ReadnPixels(
+            x as _,
+            y as _,
+            width as _,
+            height as _,
+            format.to_gl_format(),
+            type_.to_gl_type(),
+            bytes as _,
+            data,
+        );
+        gl::PixelStorei(gl::PACK_ALIGNMENT, alignment);
     }
 }
 
@@ -33,9 +209,9 @@ pub struct FrameBuffers {
 impl FrameBuffers {
     #[inline]
     pub(super) fn new(count: usize) -> Self {
-        let mut fbos = Vec::with_capacity(count);
+        let mut fbos = vec![0; count];
         unsafe {
-            gl::GenFramebuffers(count as i32, fbos.as_mut_ptr());
+            gl::CreateFramebuffers(count as i32, fbos.as_mut_ptr());
         }
         Self {
             fbos: fbos.into_iter().map(|fbo| FrameBuffer { fbo }).collect(),
@@ -94,54 +270,505 @@ impl FrameBuffer {
     /// Wrapper of `glBindFramebuffer(...)`
     #[inline]
     pub fn bind(&self, target: FrameBufferTarget) {
+        Self::bind_name(target, self.fbo);
+    }
+
+    /// Bind this frame buffer to `target` until the returned guard is dropped.
+    ///
+    /// The guard restores the previously bound read and draw frame buffers and the viewport.
+    #[inline]
+    pub fn bind_scoped(&self, target: FrameBufferTarget) -> FrameBufferBinding<'_> {
+        let mut read = 0;
+        let mut draw = 0;
+        let mut viewport = [0; 4];
         unsafe {
-            gl::BindFramebuffer(target.to_gl_target(), self.fbo);
+            gl::GetIntegerv(gl::READ_FRAMEBUFFER_BINDING, &mut read);
+            gl::GetIntegerv(gl::DRAW_FRAMEBUFFER_BINDING, &mut draw);
+            gl::GetIntegerv(gl::VIEWPORT, viewport.as_mut_ptr());
+        }
+        self.bind(target);
+        FrameBufferBinding {
+            read: read as _,
+            draw: draw as _,
+            viewport,
+            _frame_buffer: PhantomData,
         }
     }
 
     /// Wrapper of `glBindFramebuffer(...)`
     #[inline]
     pub fn unbind(target: FrameBufferTarget) {
+        Self::bind_name(target, 0);
+    }
+
+    #[inline]
+    fn bind_name(target: FrameBufferTarget, fbo: GLuint) {
+        let read = Slot::FrameBuffer(gl::READ_FRAMEBUFFER);
+        let draw = Slot::FrameBuffer(gl::DRAW_FRAMEBUFFER);
+        let slots: &[Slot] = match target {
+            FrameBufferTarget::Read => &[read],
+            FrameBufferTarget::Draw => &[draw],
+            FrameBufferTarget::ReadDraw => &[read, draw],
+        };
+        if !state::update_all(slots, &[fbo]) {
+            return;
+        }
+        unsafe {
+            gl::BindFramebuffer(target.to_gl_target(), fbo);
+        }
+    }
+
+    #[inline]
+    fn is_default(&self) -> bool {
+        self.fbo == 0
+    }
+
+    /// Wrapper of `glInvalidateNamedFramebufferData(...)`
+    ///
+    /// Discard the contents of `attachments`, which are not needed anymore.
+    #[inline]
+    pub fn invalidate(&self, attachments: &[FrameBufferAttachment]) -> Result<(), String> {
+        let attachments = self.invalidate_attachments(attachments)?;
         unsafe {
-            gl::BindFramebuffer(target.to_gl_target(), 0);
+            gl::InvalidateNamedFramebufferData(
+                self.fbo,
+                attachments.len() as _,
+                attachments.as_ptr(),
+            );
         }
+        check_error("Invalidate Frame Buffer Error")
     }
 
-    /// Wrapper of `glFramebufferRenderbuffer(...)`
+    /// Wrapper of `glInvalidateNamedFramebufferSubData(...)`
+    ///
+    /// Discard the contents of `attachments` in `((x, y), (width, height))`.
     #[inline]
+    pub fn invalidate_region(
+        &self,
+        attachments: &[FrameBufferAttachment],
+        ((x, y), (width, height)): ((u32, u32), (u32, u32)),
+    ) -> Result<(), String> {
+        let attachments = self.invalidate_attachments(attachments)?;
+        unsafe {
+            gl::InvalidateNamedFramebufferSubData(
+                self.fbo,
+                attachments.len() as _,
+                attachments.as_ptr(),
+                x as _,
+                y as _,
+                width as _,
+                height as _,
+            );
+        }
+        check_error("Invalidate Frame Buffer Error")
+    }
+
+    /// The default frame buffer names its buffers `GL_COLOR`, `GL_DEPTH` and `GL_STENCIL`.
+    fn invalidate_attachments(
+        &self,
+        attachments: &[FrameBufferAttachment],
+    ) -> Result<Vec<GLenum>, String> {
+        let mut names = Vec::with_capacity(attachments.len());
+        for &attachment in attachments {
+            if !self.is_default() {
+                check_attachment(attachment)?;
+                names.push(attachment.to_gl_attachment());
+                continue;
+            }
+            match attachment {
+                FrameBufferAttachment::Color(_) => names.push(gl::COLOR),
+                FrameBufferAttachment::Depth => names.push(gl::DEPTH),
+                FrameBufferAttachment::Stencil => names.push(gl::STENCIL),
+                FrameBufferAttachment::DepthStencil => names.extend([gl::DEPTH, gl::STENCIL]),
+            }
+        }
+        Ok(names)
+    }
+
+    /// Wrapper of `glNamedFramebufferDrawBuffers(...)`
+    ///
+    /// Fragment output `i` is written to `attachments[i]`, which must be a color attachment,
+    /// or discarded if it is `None`.
+    pub fn draw_buffers(
+        &self,
+        attachments: &[Option<FrameBufferAttachment>],
+    ) -> Result<(), String> {
+        let mut buffers = Vec::with_capacity(attachments.len());
+        for &attachment in attachments {
+            let attachment = match attachment {
+                Some(attachment) => attachment,
+                None => {
+                    buffers.push(gl::NONE);
+                    continue;
+                }
+            };
+            if !matches!(attachment, FrameBufferAttachment::Color(_)) {
+                return Err(format!("{:?} is not a color attachment", attachment));
+            }
+            check_attachment(attachment)?;
+            buffers.push(attachment.to_gl_attachment());
+        }
+        unsafe {
+            gl::NamedFramebufferDrawBuffers(self.fbo, buffers.len() as _, buffers.as_ptr());
+        }
+        check_error("Draw Buffers Error")
+    }
+
+    /// Wrapper of `glNamedFramebufferReadBuffer(...)`
+    ///
+    /// Select the color attachment read by `glReadPixels(...)` and blits.
+    pub fn read_buffer(&self, attachment: FrameBufferAttachment) -> Result<(), String> {
+        if !matches!(attachment, FrameBufferAttachment::Color(_)) {
+            return Err(format!("{:?} is not a color attachment", attachment));
+        }
+        check_attachment(attachment)?;
+        unsafe {
+            gl::NamedFramebufferReadBuffer(self.fbo, attachment.to_gl_attachment());
+        }
+        check_error("Read Buffer Error")
+    }
+
+    /// Wrapper of `glClearNamedFramebufferfv(...)` for a color buffer.
+    ///
+    /// `draw_buffer` is the index in the list set by `draw_buffers(...)`,
+    /// not the index of the color attachment.
+    #[inline]
+    pub fn clear_color_f(&self, draw_buffer: u32, color: [f32; 4]) {
+        unsafe {
+            gl::ClearNamedFramebufferfv(self.fbo, gl::COLOR, draw_buffer as _, color.as_ptr());
+        }
+    }
+
+    /// Wrapper of `glClearNamedFramebufferiv(...)` for a signed integer color buffer.
+    #[inline]
+    pub fn clear_color_i(&self, draw_buffer: u32, color: [i32; 4]) {
+        unsafe {
+            gl::ClearNamedFramebufferiv(self.fbo, gl::COLOR, draw_buffer as _, color.as_ptr());
+        }
+    }
+
+    /// Wrapper of `glClearNamedFramebufferuiv(...)` for an unsigned integer color buffer.
+    #[inline]
+    pub fn clear_color_u(&self, draw_buffer: u32, color: [u32; 4]) {
+        unsafe {
+            gl::ClearNamedFramebufferuiv(self.fbo, gl::COLOR, draw_buffer as _, color.as_ptr());
+        }
+    }
+
+    /// Wrapper of `glClearNamedFramebufferfv(...)` for the depth buffer.
+    #[inline]
+    pub fn clear_depth(&self, depth: f32) {
+        unsafe {
+            gl::ClearNamedFramebufferfv(self.fbo, gl::DEPTH, 0, &depth);
+        }
+    }
+
+    /// Wrapper of `glClearNamedFramebufferiv(...)` for the stencil buffer.
+    #[inline]
+    pub fn clear_stencil(&self, stencil: i32) {
+        unsafe {
+            gl::ClearNamedFramebufferiv(self.fbo, gl::STENCIL, 0, &stencil);
+        }
+    }
+
+    /// Wrapper of `glClearNamedFramebufferfi(...)`
+    #[inline]
+    pub fn clear_depth_stencil(&self, depth: f32, stencil: i32) {
+        unsafe {
+            gl::ClearNamedFramebufferfi(self.fbo, gl::DEPTH_STENCIL, 0, depth, stencil);
+        }
+    }
+
+    /// Wrapper of `glNamedFramebufferTexture(...)`
+    ///
+    /// Attach the mipmap `level` of `texture`. A layered texture is attached as a whole.
+    /// It will return `Err(String)` if layered and non-layered attachments would be mixed.
+    pub fn attach_texture(
+        &self,
+        attachment: FrameBufferAttachment,
+        texture: &Texture,
+        level: u32,
+    ) -> Result<(), String> {
+        check_attachment(attachment)?;
+        let layered = texture.target().is_some_and(TexTarget::is_layered);
+        self.check_layering(attachment, layered)?;
+        unsafe {
+            gl::NamedFramebufferTexture(
+                self.fbo,
+                attachment.to_gl_attachment(),
+                texture.id,
+                level as _,
+            );
+        }
+        check_error("Attach Texture Error")
+    }
+
+    /// Wrapper of `glNamedFramebufferTextureLayer(...)`
+    ///
+    /// Attach a single `layer` of the mipmap `level` of an array, cube map or 3D `texture`.
+    pub fn attach_texture_layer(
+        &self,
+        attachment: FrameBufferAttachment,
+        texture: &Texture,
+        level: u32,
+        layer: u32,
+    ) -> Result<(), String> {
+        check_attachment(attachment)?;
+        self.check_layering(attachment, false)?;
+        unsafe {
+            gl::NamedFramebufferTextureLayer(
+                self.fbo,
+                attachment.to_gl_attachment(),
+                texture.id,
+                level as _,
+                layer as _,
+            );
+        }
+        check_error("Attach Texture Layer Error")
+    }
+
+    /// Attach all layers of the mipmap `level` of an array, cube map or 3D `texture`.
+    ///
+    /// The layer rendered to is selected by `gl_Layer` in a geometry shader,
+    /// cube map faces are layers in the order of `TexCubeMap`.
+    pub fn attach_layered(
+        &self,
+        attachment: FrameBufferAttachment,
+        texture: &Texture,
+        level: u32,
+    ) -> Result<(), String> {
+        match texture.target() {
+            Some(target) if target.is_layered() => self.attach_texture(attachment, texture, level),
+            target => Err(format!("{:?} is not a layered texture target", target)),
+        }
+    }
+
+    /// Return whether the image attached to `attachment` is layered,
+    /// `None` if nothing is attached.
+    pub fn is_layered(&self, attachment: FrameBufferAttachment) -> Option<bool> {
+        let attachment = attachment.to_gl_attachment();
+        let mut object_type = 0;
+        unsafe {
+            gl::GetNamedFramebufferAttachmentParameteriv(
+                self.fbo,
+                attachment,
+                gl::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE,
+                &mut object_type,
+            );
+        }
+        match object_type as GLenum {
+            gl::TEXTURE => {
+                let mut layered = 0;
+                unsafe {
+                    gl::GetNamedFramebufferAttachmentParameteriv(
+                        self.fbo,
+                        attachment,
+                        gl::FRAMEBUFFER_ATTACHMENT_LAYERED,
+                        &mut layered,
+                    );
+                }
+                Some(layered != 0)
+            }
+            gl::RENDERBUFFER => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Return the size of the image attached to `attachment`, `None` if nothing is attached.
+    pub fn attachment_size(&self, attachment: FrameBufferAttachment) -> Option<(u32, u32)> {
+        let attachment = attachment.to_gl_attachment();
+        let get = |pname| {
+            let mut value = 0;
+            unsafe {
+                gl::GetNamedFramebufferAttachmentParameteriv(
+                    self.fbo, attachment, pname, &mut value,
+                );
+            }
+            value
+        };
+        let name = get(gl::FRAMEBUFFER_ATTACHMENT_OBJECT_NAME) as GLuint;
+        let (mut width, mut height) = (0, 0);
+        match get(gl::FRAMEBUFFER_ATTACHMENT_OBJECT_TYPE) as GLenum {
+            gl::TEXTURE => {
+                let level = get(gl::FRAMEBUFFER_ATTACHMENT_TEXTURE_LEVEL);
+                unsafe {
+                    gl::GetTextureLevelParameteriv(name, level, gl::TEXTURE_WIDTH, &mut width);
+                    gl::GetTextureLevelParameteriv(name, level, gl::TEXTURE_HEIGHT, &mut height);
+                }
+            }
+            gl::RENDERBUFFER => unsafe {
+                gl::GetNamedRenderbufferParameteriv(name, gl::RENDERBUFFER_WIDTH, &mut width);
+                gl::GetNamedRenderbufferParameteriv(name, gl::RENDERBUFFER_HEIGHT, &mut height);
+            },
+            _ => return None,
+        }
+        Some((width as _, height as _))
+    }
+
+    /// Return the size shared by all attached images, i.e. the smallest one,
+    /// `None` if nothing is attached.
+    fn size(&self) -> Option<(u32, u32)> {
+        (0..super::max_color_attachments())
+            .map(FrameBufferAttachment::Color)
+            .chain([FrameBufferAttachment::Depth, FrameBufferAttachment::Stencil])
+            .filter_map(|attachment| self.attachment_size(attachment))
+            .reduce(|(width, height), (other_width, other_height)| {
+                (width.min(other_width), height.min(other_height))
+            })
+    }
+
+    /// Check that attaching a `layered` image to `attachment` doesn't mix layered
+    /// and non-layered attachments, which would be `FrameBufferError::IncompleteLayerTargets`.
+    fn check_layering(
+        &self,
+        attachment: FrameBufferAttachment,
+        layered: bool,
+    ) -> Result<(), String> {
+        if self.is_default() {
+            return Ok(());
+        }
+        let replaced = |other: FrameBufferAttachment| {
+            other == attachment
+                || attachment == FrameBufferAttachment::DepthStencil
+                    && matches!(
+                        other,
+                        FrameBufferAttachment::Depth | FrameBufferAttachment::Stencil
+                    )
+        };
+        let others = (0..super::max_color_attachments())
+            .map(FrameBufferAttachment::Color)
+            .chain([FrameBufferAttachment::Depth, FrameBufferAttachment::Stencil]);
+        for other in others.filter(|&other| !replaced(other)) {
+            match self.is_layered(other) {
+                Some(other_layered) if other_layered != layered => {
+                    return Err(format!(
+                        "Can't attach a {} image to {:?}, {:?} is {}",
+                        if layered { "layered" } else { "non-layered" },
+                        attachment,
+                        other,
+                        if other_layered {
+                            "layered"
+                        } else {
+                            "non-layered"
+                        }
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Wrapper of `glNamedFramebufferRenderbuffer(...)`
     pub fn attach_render_buffer(
-        target: FrameBufferTarget,
-        attachment: Attachmect,
+        &self,
+        attachment: FrameBufferAttachment,
         render_buffer: &RenderBuffer,
-    ) {
+    ) -> Result<(), String> {
+        check_attachment(attachment)?;
+        self.check_layering(attachment, false)?;
         unsafe {
-            gl::FramebufferRenderbuffer(
-                target.to_gl_target(),
+            gl::NamedFramebufferRenderbuffer(
+                self.fbo,
                 attachment.to_gl_attachment(),
                 gl::RENDERBUFFER,
                 render_buffer.rbo,
             );
         }
+        check_error("Attach Render Buffer Error")
     }
 
-    /// Wrapper of `glCheckFramebufferStatus(...)`
+    /// Remove the image attached to `attachment`.
     #[inline]
-    pub fn check_status(target: FrameBufferTarget) -> Result<(), String> {
-        let err = unsafe { gl::CheckFramebufferStatus(target.to_gl_target()) };
-        if err == gl::FRAMEBUFFER_COMPLETE {
-            Ok(())
-        } else {
-            Err(format!("Not complete, error code: {}", err))
+    pub fn detach(&self, attachment: FrameBufferAttachment) {
+        unsafe {
+            gl::NamedFramebufferTexture(self.fbo, attachment.to_gl_attachment(), 0, 0);
         }
     }
 
+    /// Wrapper of `glCheckNamedFramebufferStatus(...)`
+    #[inline]
+    pub fn check(&self) -> Result<(), FrameBufferError> {
+        let status = unsafe { gl::CheckNamedFramebufferStatus(self.fbo, gl::FRAMEBUFFER) };
+        FrameBufferError::from_gl_status(status).map_or(Ok(()), Err)
+    }
+
+    /// Wrapper of `glCheckFramebufferStatus(...)`
+    #[inline]
+    pub fn check_status(target: FrameBufferTarget) -> Result<(), FrameBufferError> {
+        let status = unsafe { gl::CheckFramebufferStatus(target.to_gl_target()) };
+        FrameBufferError::from_gl_status(status).map_or(Ok(()), Err)
+    }
+
+    /// Wrapper of `glReadnPixels(...)`
+    ///
+    /// Read the pixels of `((x, y), (width, height))` from the read buffer, see `read_buffer(...)`.
+    /// Rows are tightly packed from the bottom up. It will return `Err(String)` if the byte size
+    /// of the pixels is not a multiple of the size of `T`, or `T` is zero-sized.
+    #[allow(private_bounds)]
+    pub fn read_pixels<T: PlainData>(
+        &self,
+        rect: ((u32, u32), (u32, u32)),
+        format: ImageFormat,
+        type_: PixelDataType,
+    ) -> Result<Vec<T>, String> {
+        let bytes = pixels_bytes::<T>(rect.1, format, type_)?;
+        let mut pixels = vec![T::ZERO; bytes / std::mem::size_of::<T>()];
+        let _binding = self.bind_scoped(FrameBufferTarget::Read);
+        let _pack = PixelPackBinding::new(0);
+        read_pixels(rect, format, type_, bytes, pixels.as_mut_ptr() as _);
+        check_error("Read Pixels Error")?;
+        Ok(pixels)
+    }
+
+    /// Read the pixels of `((x, y), (width, height))` into a `Target::PixelPack` buffer
+    /// without waiting for the GPU.
+    ///
+    /// The returned transfer can be polled by `PixelTransfer::is_ready()`.
+    pub fn read_pixels_async(
+        &self,
+        rect: ((u32, u32), (u32, u32)),
+        format: ImageFormat,
+        type_: PixelDataType,
+    ) -> Result<PixelTransfer, String> {
+        let buffer = Buffer::new();
+        buffer.allocate(
+            pixels_bytes::<u8>(rect.1, format, type_)?,
+            Usage::StreamRead,
+        );
+        self.read_pixels_into(&buffer, rect, format, type_)?;
+        Ok(PixelTransfer {
+            buffer,
+            fence: Fence::new(),
+            size: rect.1,
+            format,
+            type_,
+        })
+    }
+
+    /// Read the pixels of `((x, y), (width, height))` into the beginning of `buffer`,
+    /// which must be large enough, without waiting for the GPU.
+    pub fn read_pixels_into(
+        &self,
+        buffer: &Buffer,
+        rect: ((u32, u32), (u32, u32)),
+        format: ImageFormat,
+        type_: PixelDataType,
+    ) -> Result<(), String> {
+        let bytes = pixels_bytes::<u8>(rect.1, format, type_)?;
+        let _binding = self.bind_scoped(FrameBufferTarget::Read);
+        let _pack = PixelPackBinding::new(buffer.buffer);
+        read_pixels(rect, format, type_, bytes, std::ptr::null_mut());
+        check_error("Read Pixels Error")
+    }
+
     /// Wrapper of `glBlitNamedFramebuffer(...)`
     pub fn blit_from(
         &self,
         frame_buffer: &FrameBuffer,
         ((src_x0, src_y0), (src_x1, src_y1)): ((u32, u32), (u32, u32)),
         ((dst_x0, dst_y0), (dst_x1, dst_y1)): ((u32, u32), (u32, u32)),
-        mask: u32,
+        mask: ClearMask,
         filter: Filter,
     ) {
         unsafe {
@@ -156,17 +783,31 @@ impl FrameBuffer {
                 dst_y0 as _,
                 dst_x1 as _,
                 dst_y1 as _,
-                mask,
+                mask.to_gl_mask(),
                 filter.to_gl_filter(),
             );
         }
     }
 
+    /// Resolve the `mask` buffers of this multisample frame buffer into `frame_buffer`,
+    /// over the size of its attached images.
+    ///
+    /// The buffers are blitted with `Filter::Nearest`,
+    /// which is the only filter accepted for depth, stencil and integer color buffers.
+    /// It will return `Err(String)` if nothing is attached to this frame buffer.
+    pub fn resolve_into(&self, frame_buffer: &FrameBuffer, mask: ClearMask) -> Result<(), String> {
+        let size = self
+            .size()
+            .ok_or_else(|| "Can't resolve a frame buffer without attachments".to_string())?;
+        frame_buffer.blit_from(self, ((0, 0), size), ((0, 0), size), mask, Filter::Nearest);
+        check_error("Resolve Error")
+    }
+
     /// Wrapper of `glBlitFramebuffer(...)`
     pub fn blit(
         ((src_x0, src_y0), (src_x1, src_y1)): ((u32, u32), (u32, u32)),
         ((dst_x0, dst_y0), (dst_x1, dst_y1)): ((u32, u32), (u32, u32)),
-        mask: u32,
+        mask: ClearMask,
         filter: Filter,
     ) {
         unsafe {
@@ -179,9 +820,144 @@ impl FrameBuffer {
                 dst_y0 as _,
                 dst_x1 as _,
                 dst_y1 as _,
-                mask,
+                mask.to_gl_mask(),
                 filter.to_gl_filter(),
             );
         }
     }
 }
+
+impl DefaultFrameBuffer {
+    /// Wrapper of `glBindFramebuffer(...)`
+    #[inline]
+    pub fn bind(&self, target: FrameBufferTarget) {
+        DEFAULT_FRAME_BUFFER.bind(target);
+    }
+
+    /// Bind the default frame buffer to `target` until the returned guard is dropped,
+    /// see `FrameBuffer::bind_scoped(...)`.
+    #[inline]
+    pub fn bind_scoped(&self, target: FrameBufferTarget) -> FrameBufferBinding<'static> {
+        DEFAULT_FRAME_BUFFER.bind_scoped(target)
+    }
+
+    /// Wrapper of `glInvalidateNamedFramebufferData(...)`
+    #[inline]
+    pub fn invalidate(&self, attachments: &[FrameBufferAttachment]) -> Result<(), String> {
+        DEFAULT_FRAME_BUFFER.invalidate(attachments)
+    }
+
+    /// Wrapper of `glInvalidateNamedFramebufferSubData(...)`
+    #[inline]
+    pub fn invalidate_region(
+        &self,
+        attachments: &[FrameBufferAttachment],
+        rect: ((u32, u32), (u32, u32)),
+    ) -> Result<(), String> {
+        DEFAULT_FRAME_BUFFER.invalidate_region(attachments, rect)
+    }
+
+    /// Wrapper of `glNamedFramebufferDrawBuffer(...)`, `None` discards the fragment colors.
+    #[inline]
+    pub fn draw_buffer(&self, buffer: Option<DefaultBuffer>) -> Result<(), String> {
+        let buffer = buffer.map_or(gl::NONE, DefaultBuffer::to_gl_buffer);
+        unsafe {
+            gl::NamedFramebufferDrawBuffer(0, buffer);
+        }
+        check_error("Draw Buffer Error")
+    }
+
+    /// Wrapper of `glNamedFramebufferReadBuffer(...)`
+    #[inline]
+    pub fn read_buffer(&self, buffer: DefaultBuffer) -> Result<(), String> {
+        unsafe {
+            gl::NamedFramebufferReadBuffer(0, buffer.to_gl_buffer());
+        }
+        check_error("Read Buffer Error")
+    }
+
+    /// Wrapper of `glClearNamedFramebufferfv(...)` for the color buffer.
+    #[inline]
+    pub fn clear_color(&self, color: [f32; 4]) {
+        DEFAULT_FRAME_BUFFER.clear_color_f(0, color);
+    }
+
+    /// Wrapper of `glClearNamedFramebufferfv(...)` for the depth buffer.
+    #[inline]
+    pub fn clear_depth(&self, depth: f32) {
+        DEFAULT_FRAME_BUFFER.clear_depth(depth);
+    }
+
+    /// Wrapper of `glClearNamedFramebufferiv(...)` for the stencil buffer.
+    #[inline]
+    pub fn clear_stencil(&self, stencil: i32) {
+        DEFAULT_FRAME_BUFFER.clear_stencil(stencil);
+    }
+
+    /// Wrapper of `glClearNamedFramebufferfi(...)`
+    #[inline]
+    pub fn clear_depth_stencil(&self, depth: f32, stencil: i32) {
+        DEFAULT_FRAME_BUFFER.clear_depth_stencil(depth, stencil);
+    }
+
+    /// Read pixels from the read buffer, see `FrameBuffer::read_pixels(...)`.
+    #[allow(private_bounds)]
+    #[inline]
+    pub fn read_pixels<T: PlainData>(
+        &self,
+        rect: ((u32, u32), (u32, u32)),
+        format: ImageFormat,
+        type_: PixelDataType,
+    ) -> Result<Vec<T>, String> {
+        DEFAULT_FRAME_BUFFER.read_pixels(rect, format, type_)
+    }
+
+    /// Read pixels without waiting for the GPU, see `FrameBuffer::read_pixels_async(...)`.
+    #[inline]
+    pub fn read_pixels_async(
+        &self,
+        rect: ((u32, u32), (u32, u32)),
+        format: ImageFormat,
+        type_: PixelDataType,
+    ) -> Result<PixelTransfer, String> {
+        DEFAULT_FRAME_BUFFER.read_pixels_async(rect, format, type_)
+    }
+
+    /// Read pixels into `buffer`, see `FrameBuffer::read_pixels_into(...)`.
+    #[inline]
+    pub fn read_pixels_into(
+        &self,
+        buffer: &Buffer,
+        rect: ((u32, u32), (u32, u32)),
+        format: ImageFormat,
+        type_: PixelDataType,
+    ) -> Result<(), String> {
+        DEFAULT_FRAME_BUFFER.read_pixels_into(buffer, rect, format, type_)
+    }
+
+    /// Wrapper of `glBlitNamedFramebuffer(...)` from `frame_buffer` to the window surface.
+    #[inline]
+    pub fn blit_from(
+        &self,
+        frame_buffer: &FrameBuffer,
+        src: ((u32, u32), (u32, u32)),
+        dst: ((u32, u32), (u32, u32)),
+        mask: ClearMask,
+        filter: Filter,
+    ) {
+        DEFAULT_FRAME_BUFFER.blit_from(frame_buffer, src, dst, mask, filter);
+    }
+
+    /// Wrapper of `glBlitNamedFramebuffer(...)` from the window surface to `frame_buffer`.
+    #[inline]
+    pub fn blit_into(
+        &self,
+        frame_buffer: &FrameBuffer,
+        src: ((u32, u32), (u32, u32)),
+        dst: ((u32, u32), (u32, u32)),
+        mask: ClearMask,
+        filter: Filter,
+    ) {
+        frame_buffer.blit_from(&DEFAULT_FRAME_BUFFER, src, dst, mask, filter);
+    }
+}
@@ -0,0 +1,336 @@
+use std::marker::PhantomData;
+
+use gl::types::{GLenum, GLint};
+
+use super::{
+    pipeline,
+    state::{self, Slot},
+    Context,
+};
+
+#[inline]
+fn get_ints<const N: usize>(pname: GLenum) -> [GLint; N] {
+    let mut values = [0; N];
+    unsafe { gl::GetIntegerv(pname, values.as_mut_ptr()) };
+    values
+}
+
+/// Number of indices of `cap` that can be set by `glEnablei(...)`, `0` if not indexed.
+fn indexed_count(cap: GLenum) -> u32 {
+    let pname = match cap {
+        gl::BLEND => gl::MAX_DRAW_BUFFERS,
+        gl::SCISSOR_TEST => gl::MAX_VIEWPORTS,
+        _ => return 0,
+    };
+    let [count] = get_ints(pname);
+    count as _
+}
+
+/// Return whether `cap` is enabled, for each index if it is indexed.
+fn is_enabled(cap: GLenum) -> Vec<bool> {
+    match indexed_count(cap) {
+        0 => vec![unsafe { gl::IsEnabled(cap) != 0 }],
+        count => (0..count)
+            .map(|index| unsafe { gl::IsEnabledi(cap, index) != 0 })
+            .collect(),
+    }
+}
+
+/// Restore `cap` to `enabled`, for each index if it differs, keeping the state cache in sync.
+fn restore_cap(cap: GLenum, enabled: &[bool]) {
+    unsafe {
+        match enabled {
+            [true, rest @ ..] if rest.iter().all(|&enabled| enabled) => gl::Enable(cap),
+            [false, rest @ ..] if rest.iter().all(|&enabled| !enabled) => gl::Disable(cap),
+            _ => {
+                for (index, &enabled) in (0..).zip(enabled) {
+                    if enabled {
+                        gl::Enablei(cap, index);
+                    } else {
+                        gl::Disablei(cap, index);
+                    }
+                }
+            }
+        }
+    }
+    match enabled {
+        [first, rest @ ..] if rest.iter().all(|enabled| enabled == first) => {
+            state::set(Slot::Cap(cap), &[*first as _]);
+        }
+        _ => state::forget(Slot::Cap(cap)),
+    }
+    pipeline::set_last_applied(None);
+}
+
+/// Guard of `Context::scoped_enable(...)` and `Context::scoped_disable(...)`,
+/// restoring the capability on drop, per draw buffer or viewport for `Blend` and `ScissorTest`.
+pub struct CapGuard<'a> {
+    cap: GLenum,
+    enabled: Vec<bool>,
+    _context: PhantomData<&'a Context>,
+}
+
+impl CapGuard<'_> {
+    #[inline]
+    pub(super) fn new(cap: GLenum) -> Self {
+        Self {
+            cap,
+            enabled: is_enabled(cap),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for CapGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        restore_cap(self.cap, &self.enabled);
+    }
+}
+
+/// Guard of `Context::scoped_viewport(...)`, restoring the viewport on drop.
+pub struct ViewportGuard<'a> {
+    viewport: [GLint; 4],
+    _context: PhantomData<&'a Context>,
+}
+
+impl ViewportGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self {
+            viewport: get_ints(gl::VIEWPORT),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for ViewportGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        let [x, y, width, height] = self.viewport;
+        unsafe { gl::Viewport(x, y, width, height) };
+    }
+}
+
+/// Blend funcs and equations of one draw buffer.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct DrawBufferBlend {
+    funcs: [GLenum; 4],
+    equations: [GLenum; 2],
+}
+
+impl DrawBufferBlend {
+    fn get(draw_buffer: u32) -> Self {
+        let get = |pname| {
+            let mut value = 0;
+            unsafe { gl::GetIntegeri_v(pname, draw_buffer, &mut value) };
+            value as GLenum
+        };
+        Self {
+            funcs: [
+                get(gl::BLEND_SRC_RGB),
+                get(gl::BLEND_DST_RGB),
+                get(gl::BLEND_SRC_ALPHA),
+                get(gl::BLEND_DST_ALPHA),
+            ],
+            equations: [get(gl::BLEND_EQUATION_RGB), get(gl::BLEND_EQUATION_ALPHA)],
+        }
+    }
+}
+
+/// Guard of `Context::scoped_blend(...)`, restoring the blending on drop,
+/// including the one set per draw buffer by `Context::enablei(...)` or `Context::blend_funci(...)`.
+pub struct BlendGuard<'a> {
+    enabled: Vec<bool>,
+    blends: Vec<DrawBufferBlend>,
+    _context: PhantomData<&'a Context>,
+}
+
+impl BlendGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self {
+            enabled: is_enabled(gl::BLEND),
+            blends: (0..indexed_count(gl::BLEND))
+                .map(DrawBufferBlend::get)
+                .collect(),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for BlendGuard<'_> {
+    fn drop(&mut self) {
+        match self.blends.as_slice() {
+            [first, rest @ ..] if rest.iter().all(|blend| blend == first) => {
+                let DrawBufferBlend { funcs, equations } = *first;
+                unsafe {
+                    gl::BlendFuncSeparate(funcs[0], funcs[1], funcs[2], funcs[3]);
+                    gl::BlendEquationSeparate(equations[0], equations[1]);
+                }
+                state::set(Slot::BlendFunc, &funcs);
+                state::set(Slot::BlendEquation, &equations);
+            }
+            blends => {
+                for (draw_buffer, blend) in (0..).zip(blends) {
+                    let DrawBufferBlend { funcs, equations } = *blend;
+                    unsafe {
+                        gl::BlendFuncSeparatei(draw_buffer, funcs[0], funcs[1], funcs[2], funcs[3]);
+                        gl::BlendEquationSeparatei(draw_buffer, equations[0], equations[1]);
+                    }
+                }
+                state::forget(Slot::BlendFunc);
+                state::forget(Slot::BlendEquation);
+            }
+        }
+        restore_cap(gl::BLEND, &self.enabled);
+    }
+}
+
+/// Guard of `Context::scoped_depth(...)`, restoring the depth test on drop.
+pub struct DepthGuard<'a> {
+    enabled: Vec<bool>,
+    func: GLint,
+    write: GLint,
+    _context: PhantomData<&'a Context>,
+}
+
+impl DepthGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let [func] = get_ints(gl::DEPTH_FUNC);
+        let [write] = get_ints(gl::DEPTH_WRITEMASK);
+        Self {
+            enabled: is_enabled(gl::DEPTH_TEST),
+            func,
+            write,
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for DepthGuard<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::DepthFunc(self.func as _);
+            gl::DepthMask((self.write != 0) as _);
+        }
+        state::set(Slot::DepthFunc, &[self.func as _]);
+        state::set(Slot::DepthMask, &[(self.write != 0) as _]);
+        restore_cap(gl::DEPTH_TEST, &self.enabled);
+    }
+}
+
+/// Guard of `Context::scoped_cull(...)`, restoring the face culling on drop.
+pub struct CullGuard<'a> {
+    enabled: Vec<bool>,
+    face: GLint,
+    _context: PhantomData<&'a Context>,
+}
+
+impl CullGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let [face] = get_ints(gl::CULL_FACE_MODE);
+        Self {
+            enabled: is_enabled(gl::CULL_FACE),
+            face,
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for CullGuard<'_> {
+    fn drop(&mut self) {
+        unsafe { gl::CullFace(self.face as _) };
+        state::set(Slot::CullFace, &[self.face as _]);
+        restore_cap(gl::CULL_FACE, &self.enabled);
+    }
+}
+
+/// Guard of `Context::scoped_color_mask(...)`, restoring the color write mask on drop.
+pub struct ColorMaskGuard<'a> {
+    mask: [GLint; 4],
+    _context: PhantomData<&'a Context>,
+}
+
+impl ColorMaskGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self {
+            mask: get_ints(gl::COLOR_WRITEMASK),
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for ColorMaskGuard<'_> {
+    fn drop(&mut self) {
+        let mask = self.mask.map(|value| (value != 0) as u32);
+        unsafe { gl::ColorMask(mask[0] as _, mask[1] as _, mask[2] as _, mask[3] as _) };
+        state::set(Slot::ColorMask, &mask);
+        pipeline::set_last_applied(None);
+    }
+}
+
+/// Guard of `Context::scoped_depth_mask(...)`, restoring the depth write mask on drop.
+pub struct DepthMaskGuard<'a> {
+    write: GLint,
+    _context: PhantomData<&'a Context>,
+}
+
+impl DepthMaskGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let [write] = get_ints(gl::DEPTH_WRITEMASK);
+        Self {
+            write,
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for DepthMaskGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { gl::DepthMask((self.write != 0) as _) };
+        state::set(Slot::DepthMask, &[(self.write != 0) as _]);
+        pipeline::set_last_applied(None);
+    }
+}
+
+/// Guard of `Context::scoped_stencil_mask(...)`, restoring the stencil write masks
+/// of both faces on drop.
+pub struct StencilMaskGuard<'a> {
+    front: GLint,
+    back: GLint,
+    _context: PhantomData<&'a Context>,
+}
+
+impl StencilMaskGuard<'_> {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let [front] = get_ints(gl::STENCIL_WRITEMASK);
+        let [back] = get_ints(gl::STENCIL_BACK_WRITEMASK);
+        Self {
+            front,
+            back,
+            _context: PhantomData,
+        }
+    }
+}
+
+impl Drop for StencilMaskGuard<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            gl::StencilMaskSeparate(gl::FRONT, self.front as _);
+            gl::StencilMaskSeparate(gl::BACK, self.back as _);
+        }
+        if self.front == self.back {
+            state::set(Slot::StencilMask, &[self.front as _]);
+        } else {
+            state::forget(Slot::StencilMask);
+        }
+        pipeline::set_last_applied(None);
+    }
+}
@@ -6,23 +6,92 @@
 
 mod array;
 mod buffer;
+mod buffer_texture;
 mod context;
 mod enums;
+mod fence;
 mod frame_buffer;
+mod guard;
+mod pipeline;
 mod program;
 mod render_buffer;
+mod sampler;
 mod shader;
+mod state;
 mod texture;
 
 pub use array::{Array, Arrays};
 pub use buffer::{Buffer, Buffers};
+pub use buffer_texture::BufferTexture;
 pub use context::Context;
 pub use enums::*;
-pub use frame_buffer::{FrameBuffer, FrameBuffers};
+pub use fence::Fence;
+pub use frame_buffer::{
+    DefaultFrameBuffer, FrameBuffer, FrameBufferBinding, FrameBuffers, PixelTransfer,
+};
+pub use guard::{
+    BlendGuard, CapGuard, ColorMaskGuard, CullGuard, DepthGuard, DepthMaskGuard, StencilMaskGuard,
+    ViewportGuard,
+};
+pub use pipeline::{BlendState, DepthState, PipelineState, StencilState};
 pub use program::Program;
 pub use render_buffer::{RenderBuffer, RenderBuffers};
+pub use sampler::Sampler;
 pub use shader::Shader;
-pub use texture::{Texture, Textures};
+pub use state::StateCacheStats;
+pub use texture::{LevelInfo, Texture, Textures};
+
+/// Turn the pending `glGetError()` into a message prefixed by `what`.
+fn check_error(what: &str) -> Result<(), String> {
+    let err = unsafe { gl::GetError() };
+    let err_enum = match err {
+        gl::NO_ERROR => return Ok(()),
+        gl::INVALID_ENUM => "GL_INVALID_ENUM",
+        gl::INVALID_VALUE => "GL_INVALID_VALUE",
+        gl::INVALID_OPERATION => "GL_INVALID_OPERATION",
+        gl::OUT_OF_MEMORY => "GL_OUT_OF_MEMORY",
+        _ => "Unknown Error",
+    };
+    Err(format!("{}: {}({})", what, err, err_enum))
+}
+
+fn max_samples() -> u32 {
+    let mut max = 0;
+    unsafe { gl::GetIntegerv(gl::MAX_SAMPLES, &mut max) };
+    max as _
+}
+
+/// Check `samples` against `GL_MAX_SAMPLES`.
+fn check_samples(samples: u32) -> Result<(), String> {
+    let max = max_samples();
+    if samples > max {
+        return Err(format!(
+            "{} samples exceed the maximum of {} samples",
+            samples, max
+        ));
+    }
+    Ok(())
+}
+
+fn max_color_attachments() -> u32 {
+    let mut max = 0;
+    unsafe { gl::GetIntegerv(gl::MAX_COLOR_ATTACHMENTS, &mut max) };
+    max as _
+}
+
+/// Check the index of a color `attachment` against `GL_MAX_COLOR_ATTACHMENTS`.
+fn check_attachment(attachment: FrameBufferAttachment) -> Result<(), String> {
+    if let FrameBufferAttachment::Color(index) = attachment {
+        let max = max_color_attachments();
+        if index >= max {
+            return Err(format!(
+                "Color attachment {} exceeds the maximum of {} color attachments",
+                index, max
+            ));
+        }
+    }
+    Ok(())
+}
 
 trait GlTypeT {
     fn to_gl_type() -> gl::types::GLenum;
@@ -51,3 +120,33 @@ impl_gl_type! {
     f32 => gl::FLOAT;
     f64 => gl::DOUBLE;
 }
+
+/// Plain data whose every bit pattern is a valid value, so GL can write into it.
+trait PlainData: Copy {
+    const ZERO: Self;
+}
+
+macro_rules! impl_plain_data {
+    ($($type:ty => $zero:expr);+ $(;)?) => {
+        $(
+            impl PlainData for $type {
+                const ZERO: Self = $zero;
+            }
+        )+
+    };
+}
+
+impl_plain_data! {
+    u8 => 0;
+    i8 => 0;
+    u16 => 0;
+    i16 => 0;
+    u32 => 0;
+    i32 => 0;
+    f32 => 0.0;
+    f64 => 0.0;
+}
+
+impl<T: PlainData, const N: usize> PlainData for [T; N] {
+    const ZERO: Self = [T::ZERO; N];
+}
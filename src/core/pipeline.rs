@@ -0,0 +1,267 @@
+use std::cell::RefCell;
+
+use super::{
+    BlendEquation, BlendFactor, Cap, CompareFunc, CullFace, FrontFace, PolygonMode, StencilOp,
+};
+
+/// Blend function and equation of a `PipelineState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BlendState {
+    pub src: BlendFactor,
+    pub dst: BlendFactor,
+    pub src_alpha: BlendFactor,
+    pub dst_alpha: BlendFactor,
+    pub equation: BlendEquation,
+}
+
+impl BlendState {
+    /// `src * alpha + dst * (1 - alpha)`
+    pub const ALPHA: BlendState = BlendState {
+        src: BlendFactor::SrcAlpha,
+        dst: BlendFactor::OneMinusSrcAlpha,
+        src_alpha: BlendFactor::One,
+        dst_alpha: BlendFactor::OneMinusSrcAlpha,
+        equation: BlendEquation::FuncAdd,
+    };
+
+    /// `src + dst`
+    pub const ADDITIVE: BlendState = BlendState {
+        src: BlendFactor::One,
+        dst: BlendFactor::One,
+        src_alpha: BlendFactor::One,
+        dst_alpha: BlendFactor::One,
+        equation: BlendEquation::FuncAdd,
+    };
+}
+
+/// Depth test of a `PipelineState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DepthState {
+    pub func: CompareFunc,
+    pub write: bool,
+}
+
+/// Stencil test of a `PipelineState`, applied to both faces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StencilState {
+    pub func: CompareFunc,
+    pub reference: i32,
+    pub read_mask: u32,
+    pub write_mask: u32,
+    pub fail: StencilOp,
+    pub depth_fail: StencilOp,
+    pub pass: StencilOp,
+}
+
+/// Fixed-function state applied at once by `Context::apply(...)`.
+///
+/// A test or blending is disabled when its state is `None`, which also resets its
+/// functions and write masks to their initial values.
+/// The default value is the initial state of OpenGL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PipelineState {
+    pub blend: Option<BlendState>,
+    pub depth: Option<DepthState>,
+    pub stencil: Option<StencilState>,
+    pub cull: Option<CullFace>,
+    pub front_face: FrontFace,
+    pub color_mask: [bool; 4],
+    pub polygon_mode: PolygonMode,
+}
+
+impl Default for PipelineState {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            blend: None,
+            depth: None,
+            stencil: None,
+            cull: None,
+            front_face: FrontFace::CounterClockwise,
+            color_mask: [true; 4],
+            polygon_mode: PolygonMode::Fill,
+        }
+    }
+}
+
+const INITIAL_BLEND: BlendState = BlendState {
+    src: BlendFactor::One,
+    dst: BlendFactor::Zero,
+    src_alpha: BlendFactor::One,
+    dst_alpha: BlendFactor::Zero,
+    equation: BlendEquation::FuncAdd,
+};
+
+const INITIAL_DEPTH: DepthState = DepthState {
+    func: CompareFunc::Less,
+    write: true,
+};
+
+const INITIAL_STENCIL: StencilState = StencilState {
+    func: CompareFunc::Always,
+    reference: 0,
+    read_mask: !0,
+    write_mask: !0,
+    fail: StencilOp::Keep,
+    depth_fail: StencilOp::Keep,
+    pass: StencilOp::Keep,
+};
+
+/// A group of GL calls issued by `Context::apply(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Change {
+    Enable(Cap),
+    Disable(Cap),
+    Blend(BlendState),
+    Depth(DepthState),
+    Stencil(StencilState),
+    CullFace(CullFace),
+    FrontFace(FrontFace),
+    ColorMask([bool; 4]),
+    PolygonMode(PolygonMode),
+}
+
+/// Emit the changes turning `last`, unknown if `None`, into `state`.
+pub(super) fn diff(
+    last: Option<&PipelineState>,
+    state: &PipelineState,
+    mut emit: impl FnMut(Change),
+) {
+    let toggle = |cap, enabled| match enabled {
+        true => Change::Enable(cap),
+        false => Change::Disable(cap),
+    };
+    if last.map(|last| last.blend) != Some(state.blend) {
+        emit(toggle(Cap::Blend, state.blend.is_some()));
+        emit(Change::Blend(state.blend.unwrap_or(INITIAL_BLEND)));
+    }
+    if last.map(|last| last.depth) != Some(state.depth) {
+        emit(toggle(Cap::DepthTest, state.depth.is_some()));
+        emit(Change::Depth(state.depth.unwrap_or(INITIAL_DEPTH)));
+    }
+    if last.map(|last| last.stencil) != Some(state.stencil) {
+        emit(toggle(Cap::StencilTest, state.stencil.is_some()));
+        emit(Change::Stencil(state.stencil.unwrap_or(INITIAL_STENCIL)));
+    }
+    if last.map(|last| last.cull) != Some(state.cull) {
+        emit(toggle(Cap::CullFace, state.cull.is_some()));
+        if let Some(face) = state.cull {
+            emit(Change::CullFace(face));
+        }
+    }
+    if last.map(|last| last.front_face) != Some(state.front_face) {
+        emit(Change::FrontFace(state.front_face));
+    }
+    if last.map(|last| last.color_mask) != Some(state.color_mask) {
+        emit(Change::ColorMask(state.color_mask));
+    }
+    if last.map(|last| last.polygon_mode) != Some(state.polygon_mode) {
+        emit(Change::PolygonMode(state.polygon_mode));
+    }
+}
+
+thread_local! {
+    static LAST_APPLIED: RefCell<Option<PipelineState>> = const { RefCell::new(None) };
+}
+
+/// Return the state of the last `Context::apply(...)`, if nothing changed it since.
+pub(super) fn last_applied() -> Option<PipelineState> {
+    LAST_APPLIED.with(|last| *last.borrow())
+}
+
+pub(super) fn set_last_applied(state: Option<PipelineState>) {
+    LAST_APPLIED.with(|last| *last.borrow_mut() = state);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn changes(last: Option<&PipelineState>, state: &PipelineState) -> Vec<Change> {
+        let mut changes = Vec::new();
+        diff(last, state, |change| changes.push(change));
+        changes
+    }
+
+    #[test]
+    fn test_diff() {
+        let opaque = PipelineState {
+            depth: Some(INITIAL_DEPTH),
+            cull: Some(CullFace::Back),
+            ..Default::default()
+        };
+        let transparent = PipelineState {
+            blend: Some(BlendState::ALPHA),
+            depth: Some(DepthState {
+                func: CompareFunc::Less,
+                write: false,
+            }),
+            ..opaque
+        };
+        assert!(changes(Some(&opaque), &opaque).is_empty());
+        assert_eq!(
+            changes(Some(&opaque), &transparent),
+            [
+                Change::Enable(Cap::Blend),
+                Change::Blend(BlendState::ALPHA),
+                Change::Enable(Cap::DepthTest),
+                Change::Depth(transparent.depth.unwrap()),
+            ]
+        );
+        // Everything is issued when the last state is unknown.
+        assert_eq!(changes(None, &opaque).len(), 11);
+    }
+
+    #[test]
+    fn test_diff_resets_write_masks() {
+        let transparent = PipelineState {
+            depth: Some(DepthState {
+                func: CompareFunc::Less,
+                write: false,
+            }),
+            stencil: Some(StencilState {
+                write_mask: 0,
+                ..INITIAL_STENCIL
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            changes(Some(&transparent), &PipelineState::default()),
+            [
+                Change::Disable(Cap::DepthTest),
+                Change::Depth(DepthState {
+                    func: CompareFunc::Less,
+                    write: true,
+                }),
+                Change::Disable(Cap::StencilTest),
+                Change::Stencil(INITIAL_STENCIL),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pipeline_state_hash() {
+        let opaque = PipelineState {
+            depth: Some(DepthState {
+                func: CompareFunc::Less,
+                write: true,
+            }),
+            cull: Some(CullFace::Back),
+            ..Default::default()
+        };
+        let transparent = PipelineState {
+            blend: Some(BlendState::ALPHA),
+            depth: Some(DepthState {
+                func: CompareFunc::Less,
+                write: false,
+            }),
+            ..opaque
+        };
+        let states: HashSet<_> = [opaque, transparent, opaque].into_iter().collect();
+        assert_eq!(states.len(), 2);
+        assert_eq!(PipelineState::default().color_mask, [true; 4]);
+        assert_eq!(PipelineState::default().polygon_mode, PolygonMode::Fill);
+    }
+}
@@ -2,7 +2,10 @@ use std::ptr;
 
 use gl::{types::GLuint, INFO_LOG_LENGTH};
 
-use super::Shader;
+use super::{
+    state::{self, Slot},
+    Shader,
+};
 
 /// Shader Program Object
 pub struct Program {
@@ -23,6 +26,7 @@ impl Drop for Program {
         unsafe {
             gl::DeleteProgram(self.program);
         }
+        state::forget_name(self.program, |slot| *slot == Slot::Program);
     }
 }
 
@@ -30,6 +34,9 @@ impl Program {
     /// Use this shader program in the current context.
     #[inline]
     pub fn using(&self) {
+        if !state::update(Slot::Program, &[self.program]) {
+            return;
+        }
         unsafe {
             gl::UseProgram(self.program);
         }
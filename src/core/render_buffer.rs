@@ -1,6 +1,6 @@
 use gl::types::GLuint;
 
-use super::RenderBufferFormat;
+use super::{check_error, check_samples, RenderBufferFormat};
 
 pub struct RenderBuffer {
     pub(super) rbo: GLuint,
@@ -11,7 +11,7 @@ impl RenderBuffer {
     pub(super) fn new() -> Self {
         let mut rbo = 0;
         unsafe {
-            gl::GenRenderbuffers(1, &mut rbo);
+            gl::CreateRenderbuffers(1, &mut rbo);
         }
         Self { rbo }
     }
@@ -33,9 +33,9 @@ pub struct RenderBuffers {
 impl RenderBuffers {
     #[inline]
     pub(super) fn new(count: usize) -> Self {
-        let mut rbos = Vec::with_capacity(count);
+        let mut rbos = vec![0; count];
         unsafe {
-            gl::GenRenderbuffers(count as i32, rbos.as_mut_ptr());
+            gl::CreateRenderbuffers(count as i32, rbos.as_mut_ptr());
         }
         Self {
             rbos: rbos.into_iter().map(|rbo| RenderBuffer { rbo }).collect(),
@@ -118,4 +118,28 @@ impl RenderBuffer {
             );
         }
     }
+
+    /// Wrapper for `glNamedRenderbufferStorageMultisample(...)`
+    ///
+    /// Allocate the storage of this render buffer with `samples` samples, `0` for no multisampling.
+    /// It will return `Err(String)` if `samples` exceeds `GL_MAX_SAMPLES`.
+    #[inline]
+    pub fn storage_multisample(
+        &self,
+        samples: u32,
+        format: RenderBufferFormat,
+        (width, height): (u32, u32),
+    ) -> Result<(), String> {
+        check_samples(samples)?;
+        unsafe {
+            gl::NamedRenderbufferStorageMultisample(
+                self.rbo,
+                samples as _,
+                format.to_gl_format(),
+                width as _,
+                height as _,
+            );
+        }
+        check_error("Render Buffer Storage Error")
+    }
 }
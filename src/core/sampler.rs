@@ -0,0 +1,93 @@
+use gl::types::GLuint;
+
+use super::{TexParam, TexParamKind, TexParamPair};
+
+/// Sampler object
+///
+/// A sampler bound to a texture unit replaces the sampling parameters of
+/// the texture, so one texture can be sampled in different ways.
+pub struct Sampler {
+    pub(super) id: GLuint,
+}
+
+impl Sampler {
+    #[inline]
+    pub(super) fn new() -> Self {
+        let mut id = 0;
+        unsafe {
+            gl::CreateSamplers(1, &mut id);
+        }
+        Self { id }
+    }
+}
+
+impl Drop for Sampler {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            gl::DeleteSamplers(1, &self.id);
+        }
+    }
+}
+
+impl Sampler {
+    /// Wrapper of `glSamplerParameter{i|f|iv|fv}(...)`
+    ///
+    /// # Note
+    /// Parameters of the texture image, which are `DepthStencilMode`, `BaseLevel`,
+    /// `MaxLevel` and `Swizzle*`, are not accepted by samplers.
+    #[inline]
+    pub fn set(&self, param: TexParam) {
+        unsafe {
+            match param.to_pair() {
+                TexParamPair::GLf(pname, param) => {
+                    gl::SamplerParameterf(self.id, pname, param);
+                }
+                TexParamPair::GLi(pname, param) => {
+                    gl::SamplerParameteri(self.id, pname, param);
+                }
+                TexParamPair::GLiv(pname, param) => {
+                    gl::SamplerParameteriv(self.id, pname, param.as_ptr());
+                }
+                TexParamPair::GLfv(pname, param) => {
+                    gl::SamplerParameterfv(self.id, pname, param.as_ptr());
+                }
+                TexParamPair::GLIiv(pname, param) => {
+                    gl::SamplerParameterIiv(self.id, pname, param.as_ptr());
+                }
+                TexParamPair::GLIuiv(pname, param) => {
+                    gl::SamplerParameterIuiv(self.id, pname, param.as_ptr());
+                }
+            }
+        }
+    }
+
+    /// Wrapper of `glGetSamplerParameter{iv|fv|Iiv|Iuiv}(...)`
+    ///
+    /// Return the current value of the parameter `kind`, or `None` if `kind`
+    /// is not sampler state or the value returned by the driver is unknown.
+    #[inline]
+    pub fn get(&self, kind: TexParamKind) -> Option<TexParam> {
+        if !kind.is_sampler_state() {
+            return None;
+        }
+        let pname = kind.to_gl_pname();
+        let mut ints = [0; 4];
+        let mut floats = [0.0; 4];
+        unsafe {
+            match kind {
+                TexParamKind::BorderColorI => {
+                    gl::GetSamplerParameterIiv(self.id, pname, ints.as_mut_ptr());
+                }
+                TexParamKind::BorderColorUI => {
+                    gl::GetSamplerParameterIuiv(self.id, pname, ints.as_mut_ptr() as _);
+                }
+                kind if kind.is_float() => {
+                    gl::GetSamplerParameterfv(self.id, pname, floats.as_mut_ptr());
+                }
+                _ => gl::GetSamplerParameteriv(self.id, pname, ints.as_mut_ptr()),
+            }
+        }
+        TexParam::from_raw(kind, ints, floats)
+    }
+}
@@ -0,0 +1,164 @@
+//! Opt-in shadow of the GL state, used to skip redundant state changes.
+//!
+//! The cache is per thread, as a GL context is current on one thread at a time.
+
+use std::{cell::RefCell, collections::HashMap};
+
+use gl::types::{GLenum, GLuint};
+
+/// A piece of GL state tracked by the cache.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Slot {
+    Cap(GLenum),
+    DepthMask,
+    DepthFunc,
+    StencilMask,
+    StencilFunc,
+    StencilOp,
+    BlendFunc,
+    BlendEquation,
+    CullFace,
+    FrontFace,
+    ColorMask,
+    PolygonMode,
+    Program,
+    VertexArray,
+    Buffer(GLenum),
+    ActiveTexture,
+    Texture(u32, GLenum),
+    FrameBuffer(GLenum),
+}
+
+/// Counters of the state cache, see `Context::enable_state_cache()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StateCacheStats {
+    /// Number of state changes sent to GL.
+    pub issued: u64,
+    /// Number of redundant state changes skipped.
+    pub skipped: u64,
+}
+
+#[derive(Default)]
+struct StateCache {
+    values: HashMap<Slot, [u32; 4]>,
+    stats: StateCacheStats,
+}
+
+thread_local! {
+    static CACHE: RefCell<Option<StateCache>> = const { RefCell::new(None) };
+}
+
+#[inline]
+fn pack(value: &[u32]) -> [u32; 4] {
+    let mut packed = [0; 4];
+    packed[..value.len()].copy_from_slice(value);
+    packed
+}
+
+pub(super) fn enable() {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if cache.is_none() {
+            *cache = Some(StateCache::default());
+        }
+    });
+}
+
+pub(super) fn disable() {
+    CACHE.with(|cache| *cache.borrow_mut() = None);
+}
+
+pub(super) fn is_enabled() -> bool {
+    CACHE.with(|cache| cache.borrow().is_some())
+}
+
+/// Forget all values, the next state changes are all issued.
+pub(super) fn invalidate() {
+    CACHE.with(|cache| {
+        if let Some(cache) = cache.borrow_mut().as_mut() {
+            cache.values.clear();
+        }
+    });
+}
+
+pub(super) fn stats() -> StateCacheStats {
+    CACHE.with(|cache| {
+        cache
+            .borrow()
+            .as_ref()
+            .map(|cache| cache.stats)
+            .unwrap_or_default()
+    })
+}
+
+pub(super) fn reset_stats() {
+    CACHE.with(|cache| {
+        if let Some(cache) = cache.borrow_mut().as_mut() {
+            cache.stats = StateCacheStats::default();
+        }
+    });
+}
+
+/// Record `value` in `slot`, return whether the GL call must be issued.
+#[inline]
+pub(super) fn update(slot: Slot, value: &[u32]) -> bool {
+    update_all(&[slot], value)
+}
+
+/// Record `value` in all `slots` set by one GL call, return whether it must be issued.
+pub(super) fn update_all(slots: &[Slot], value: &[u32]) -> bool {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let cache = match cache.as_mut() {
+            Some(cache) => cache,
+            None => return true,
+        };
+        let value = pack(value);
+        if slots
+            .iter()
+            .all(|slot| cache.values.get(slot) == Some(&value))
+        {
+            cache.stats.skipped += 1;
+            return false;
+        }
+        for &slot in slots {
+            cache.values.insert(slot, value);
+        }
+        cache.stats.issued += 1;
+        true
+    })
+}
+
+/// Record `value` set by a GL call issued outside of the cache.
+pub(super) fn set(slot: Slot, value: &[u32]) {
+    CACHE.with(|cache| {
+        if let Some(cache) = cache.borrow_mut().as_mut() {
+            cache.values.insert(slot, pack(value));
+        }
+    });
+}
+
+/// Return the cached value of `slot`.
+pub(super) fn get(slot: Slot) -> Option<[u32; 4]> {
+    CACHE.with(|cache| cache.borrow().as_ref()?.values.get(&slot).copied())
+}
+
+/// Forget `slot`, the next change of it is issued.
+pub(super) fn forget(slot: Slot) {
+    CACHE.with(|cache| {
+        if let Some(cache) = cache.borrow_mut().as_mut() {
+            cache.values.remove(&slot);
+        }
+    });
+}
+
+/// Forget the binding slots selected by `kind` which hold the deleted object `name`.
+pub(super) fn forget_name(name: GLuint, kind: fn(&Slot) -> bool) {
+    CACHE.with(|cache| {
+        if let Some(cache) = cache.borrow_mut().as_mut() {
+            cache
+                .values
+                .retain(|slot, value| !(kind(slot) && value[0] == name));
+        }
+    });
+}
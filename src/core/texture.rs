@@ -1,15 +1,27 @@
-use std::ops::{Index, IndexMut};
+use std::ops::{Index, IndexMut, Range};
 
 use gl::types::GLuint;
 
 use super::{
-    FrameBufferAttachment, FrameBufferTarget, ImageFormat, ImageTarget, InternalFormat,
-    PixelDataType, TexParam, TexParamPair, TexTarget, TextureTarget,
+    check_error, check_samples,
+    state::{self, Slot},
+    CompressedFormat, Context, FrameBufferAttachment, FrameBufferTarget, ImageFormat, ImageTarget,
+    InternalFormat, PixelDataType, TexParam, TexParamKind, TexParamPair, TexTarget, TextureTarget,
 };
 
+/// Size and format of a mipmap level, see `Texture::level_info(...)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct LevelInfo {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u32,
+    /// `None` if the format is not known by this crate.
+    pub internal_format: Option<InternalFormat>,
+}
+
 /// Texture object
 pub struct Texture {
-    id: GLuint,
+    pub(super) id: GLuint,
 }
 
 impl Texture {
@@ -19,12 +31,20 @@ impl Texture {
         unsafe { gl::GenTextures(1, &mut id) };
         Self { id }
     }
+
+    #[inline]
+    pub(crate) fn create(target: TexTarget) -> Self {
+        let mut id = 0;
+        unsafe { gl::CreateTextures(target.to_gl_target(), 1, &mut id) };
+        Self { id }
+    }
 }
 
 impl Drop for Texture {
     #[inline]
     fn drop(&mut self) {
         unsafe { gl::DeleteTextures(1, &self.id) };
+        state::forget_name(self.id, |slot| matches!(slot, Slot::Texture(..)));
     }
 }
 
@@ -91,18 +111,32 @@ impl Texture {
     /// Wrapper of `glBindTexture(...)`
     #[inline]
     pub fn bind(&self, target: TexTarget) {
-        unsafe { gl::BindTexture(target.to_gl_target(), self.id) }
+        Self::bind_name(target, self.id);
     }
 
     /// Wrapper of `glBindTexture(...)`
     #[inline]
     pub fn unbind(target: TexTarget) {
-        unsafe { gl::BindTexture(target.to_gl_target(), 0) }
+        Self::bind_name(target, 0);
+    }
+
+    #[inline]
+    fn bind_name(target: TexTarget, id: GLuint) {
+        let target = target.to_gl_target();
+        if let Some([unit, ..]) = state::get(Slot::ActiveTexture) {
+            if !state::update(Slot::Texture(unit, target), &[id]) {
+                return;
+            }
+        }
+        unsafe { gl::BindTexture(target, id) }
     }
 
     /// Wrapper of `glActiveTexture(...)`
     #[inline]
     pub fn active(index: u32) {
+        if !state::update(Slot::ActiveTexture, &[index]) {
+            return;
+        }
         unsafe {
             gl::ActiveTexture(gl::TEXTURE0 + index);
         }
@@ -122,10 +156,63 @@ impl Texture {
                 TexParamPair::GLiv(pname, param) => {
                     gl::TextureParameteriv(self.id, pname, param.as_ptr());
                 }
+                TexParamPair::GLfv(pname, param) => {
+                    gl::TextureParameterfv(self.id, pname, param.as_ptr());
+                }
+                TexParamPair::GLIiv(pname, param) => {
+                    gl::TextureParameterIiv(self.id, pname, param.as_ptr());
+                }
+                TexParamPair::GLIuiv(pname, param) => {
+                    gl::TextureParameterIuiv(self.id, pname, param.as_ptr());
+                }
             }
         }
     }
 
+    /// Wrapper of `glGetTextureParameter{iv|fv|Iiv|Iuiv}(...)`
+    ///
+    /// Return the current value of the parameter `kind`,
+    /// or `None` if the value returned by the driver is unknown.
+    #[inline]
+    pub fn get(&self, kind: TexParamKind) -> Option<TexParam> {
+        let pname = kind.to_gl_pname();
+        let mut ints = [0; 4];
+        let mut floats = [0.0; 4];
+        unsafe {
+            match kind {
+                TexParamKind::BorderColorI => {
+                    gl::GetTextureParameterIiv(self.id, pname, ints.as_mut_ptr());
+                }
+                TexParamKind::BorderColorUI => {
+                    gl::GetTextureParameterIuiv(self.id, pname, ints.as_mut_ptr() as _);
+                }
+                kind if kind.is_float() => {
+                    gl::GetTextureParameterfv(self.id, pname, floats.as_mut_ptr());
+                }
+                _ => gl::GetTextureParameteriv(self.id, pname, ints.as_mut_ptr()),
+            }
+        }
+        TexParam::from_raw(kind, ints, floats)
+    }
+
+    /// Wrapper of `glGetTextureLevelParameteriv(...)`
+    ///
+    /// Return the size and the internal format of the mipmap `level`.
+    #[inline]
+    pub fn level_info(&self, level: u32) -> LevelInfo {
+        let get = |pname| {
+            let mut value = 0;
+            unsafe { gl::GetTextureLevelParameteriv(self.id, level as _, pname, &mut value) };
+            value
+        };
+        LevelInfo {
+            width: get(gl::TEXTURE_WIDTH) as _,
+            height: get(gl::TEXTURE_HEIGHT) as _,
+            depth: get(gl::TEXTURE_DEPTH) as _,
+            internal_format: InternalFormat::from_gl_format(get(gl::TEXTURE_INTERNAL_FORMAT) as _),
+        }
+    }
+
     /// Wrapper of `glGenerateTextureMipmap(...)`
     #[inline]
     pub fn gen_minmap(&self) {
@@ -144,7 +231,7 @@ impl Texture {
         type_: PixelDataType,
         data: &[T],
     ) -> Result<(), String> {
-        let err = unsafe {
+        unsafe {
             gl::TexImage2D(
                 target.to_gl_target(),
                 0,
@@ -156,16 +243,8 @@ impl Texture {
                 type_.to_gl_type(),
                 data.as_ptr() as _,
             );
-            gl::GetError()
-        };
-        let err_enum = match err {
-            gl::NO_ERROR => return Ok(()),
-            gl::INVALID_ENUM => "GL_INVALID_ENUM",
-            gl::INVALID_VALUE => "GL_INVALID_VALUE",
-            gl::INVALID_OPERATION => "GL_INVALID_OPERATION",
-            _ => "Unknown Error",
-        };
-        Err(format!("Load Texture Error: {}({})", err, err_enum))
+        }
+        check_error("Load Texture Error")
     }
 
     /// Wrapper of `glTexImage2D(...)` without error check
@@ -209,4 +288,412 @@ impl Texture {
             );
         }
     }
+
+    /// Wrapper of `glTextureStorage2D(...)`
+    ///
+    /// Allocate immutable storage of `levels` mipmap levels. The texture must have been
+    /// created with a target, see `Context::create_texture(...)`.
+    #[inline]
+    pub fn storage_2d(
+        &self,
+        levels: u32,
+        internal_format: InternalFormat,
+        (width, height): (u32, u32),
+    ) -> Result<(), String> {
+        unsafe {
+            gl::TextureStorage2D(
+                self.id,
+                levels as _,
+                internal_format.to_gl_format(),
+                width as _,
+                height as _,
+            );
+        }
+        check_error("Texture Storage Error")
+    }
+
+    /// Wrapper of `glTextureStorage2DMultisample(...)`
+    ///
+    /// Allocate immutable storage of a `TexTarget::Tex2DMultisample` texture.
+    /// It will return `Err(String)` if `samples` exceeds `GL_MAX_SAMPLES`.
+    #[inline]
+    pub fn storage_2d_multisample(
+        &self,
+        samples: u32,
+        internal_format: InternalFormat,
+        (width, height): (u32, u32),
+        fixed_sample_locations: bool,
+    ) -> Result<(), String> {
+        if samples == 0 {
+            return Err("A multisample texture needs at least one sample".to_string());
+        }
+        check_samples(samples)?;
+        unsafe {
+            gl::TextureStorage2DMultisample(
+                self.id,
+                samples as _,
+                internal_format.to_gl_format(),
+                width as _,
+                height as _,
+                fixed_sample_locations as _,
+            );
+        }
+        check_error("Texture Storage Error")
+    }
+
+    /// Wrapper of `glTextureStorage3D(...)`
+    ///
+    /// Allocate immutable storage of `levels` mipmap levels for a 3D texture or
+    /// `depth` layers of an array texture. For cube map arrays, `depth` counts faces.
+    #[inline]
+    pub fn storage_3d(
+        &self,
+        levels: u32,
+        internal_format: InternalFormat,
+        (width, height, depth): (u32, u32, u32),
+    ) -> Result<(), String> {
+        unsafe {
+            gl::TextureStorage3D(
+                self.id,
+                levels as _,
+                internal_format.to_gl_format(),
+                width as _,
+                height as _,
+                depth as _,
+            );
+        }
+        check_error("Texture Storage Error")
+    }
+
+    /// Wrapper of `glTexImage3D(...)`
+    #[inline]
+    pub fn load_3d<T>(
+        target: TexTarget,
+        internal_format: InternalFormat,
+        (width, height, depth): (u32, u32, u32),
+        format: ImageFormat,
+        type_: PixelDataType,
+        data: &[T],
+    ) -> Result<(), String> {
+        unsafe {
+            gl::TexImage3D(
+                target.to_gl_target(),
+                0,
+                internal_format.to_gl_format() as _,
+                width as _,
+                height as _,
+                depth as _,
+                0,
+                format.to_gl_format(),
+                type_.to_gl_type(),
+                data.as_ptr() as _,
+            );
+        }
+        check_error("Load Texture Error")
+    }
+
+    /// Wrapper of `glTextureSubImage3D(...)`
+    ///
+    /// Upload `data` to a region of the mipmap `level`, where `z` is the first
+    /// layer of an array texture or the first slice of a 3D texture.
+    /// Rows of `data` are tightly packed.
+    #[inline]
+    pub fn sub_image_3d<T>(
+        &self,
+        level: u32,
+        (x, y, z): (u32, u32, u32),
+        (width, height, depth): (u32, u32, u32),
+        format: ImageFormat,
+        type_: PixelDataType,
+        data: &[T],
+    ) -> Result<(), String> {
+        Self::check_unpacked(
+            (width, height, depth),
+            format,
+            type_,
+            std::mem::size_of_val(data),
+        )?;
+        // The size is checked for tightly packed rows, the alignment of the application
+        // is restored afterwards.
+        let mut alignment = 0;
+        unsafe {
+            gl::GetIntegerv(gl::UNPACK_ALIGNMENT, &mut alignment);
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, 1);
+            gl::TextureSubImage3D(
+                self.id,
+                level as _,
+                x as _,
+                y as _,
+                z as _,
+                width as _,
+                height as _,
+                depth as _,
+                format.to_gl_format(),
+                type_.to_gl_type(),
+                data.as_ptr() as _,
+            );
+            gl::PixelStorei(gl::UNPACK_ALIGNMENT, alignment);
+        }
+        check_error("Load Texture Error")
+    }
+
+    /// Wrapper of `glClearTexImage(...)`
+    ///
+    /// Fill the mipmap `level` with the texel `value`, which is cleared to zero if empty.
+    /// Integer textures, e.g. storage images of `SizedFormat::R32UI`, take an integer `format`.
+    #[inline]
+    pub fn clear<T>(
+        &self,
+        level: u32,
+        format: ImageFormat,
+        type_: PixelDataType,
+        value: &[T],
+    ) -> Result<(), String> {
+        let value = Self::texel_value(format, type_, value)?;
+        unsafe {
+            gl::ClearTexImage(
+                self.id,
+                level as _,
+                format.to_gl_format(),
+                type_.to_gl_type(),
+                value as _,
+            );
+        }
+        check_error("Clear Texture Error")
+    }
+
+    /// Wrapper of `glClearTexSubImage(...)`
+    ///
+    /// Fill a region of the mipmap `level` with the texel `value`, which is cleared to zero if empty.
+    #[inline]
+    pub fn clear_sub<T>(
+        &self,
+        level: u32,
+        (x, y, z): (u32, u32, u32),
+        (width, height, depth): (u32, u32, u32),
+        format: ImageFormat,
+        type_: PixelDataType,
+        value: &[T],
+    ) -> Result<(), String> {
+        let value = Self::texel_value(format, type_, value)?;
+        unsafe {
+            gl::ClearTexSubImage(
+                self.id,
+                level as _,
+                x as _,
+                y as _,
+                z as _,
+                width as _,
+                height as _,
+                depth as _,
+                format.to_gl_format(),
+                type_.to_gl_type(),
+                value as _,
+            );
+        }
+        check_error("Clear Texture Error")
+    }
+
+    /// Return the target the texture was created with or first bound to,
+    /// or `None` if it has never been bound.
+    #[inline]
+    pub fn target(&self) -> Option<TexTarget> {
+        // A generated name is not a texture object until bound, and querying it
+        // would leave a pending `GL_INVALID_OPERATION`.
+        if unsafe { gl::IsTexture(self.id) } == gl::FALSE {
+            return None;
+        }
+        let mut target = 0;
+        unsafe {
+            gl::GetTextureParameteriv(self.id, gl::TEXTURE_TARGET, &mut target);
+        }
+        TexTarget::from_gl_target(target as _)
+    }
+
+    /// Wrapper of `glTextureView(...)`
+    ///
+    /// Create a texture sharing the `levels` and `layers` of this texture's immutable storage,
+    /// reinterpreted as `target` and `internal_format`. For cube maps, the layers are faces.
+    #[inline]
+    pub fn view(
+        &self,
+        context: &Context,
+        target: TexTarget,
+        internal_format: InternalFormat,
+        levels: Range<u32>,
+        layers: Range<u32>,
+    ) -> Result<Texture, String> {
+        let view = context.new_texture();
+        unsafe {
+            gl::TextureView(
+                view.id,
+                target.to_gl_target(),
+                self.id,
+                internal_format.to_gl_format(),
+                levels.start,
+                levels.len() as _,
+                layers.start,
+                layers.len() as _,
+            );
+        }
+        check_error("Texture View Error")?;
+        Ok(view)
+    }
+
+    /// Check that `len` bytes hold a region of `size` texels with tightly packed rows.
+    fn check_unpacked(
+        (width, height, depth): (u32, u32, u32),
+        format: ImageFormat,
+        type_: PixelDataType,
+        len: usize,
+    ) -> Result<(), String> {
+        let expected =
+            width as usize * height as usize * depth as usize * format.pixel_bytes(type_);
+        if len < expected {
+            return Err(format!(
+                "Region of {}x{}x{} expects {} bytes, but got {} bytes",
+                width, height, depth, expected, len
+            ));
+        }
+        Ok(())
+    }
+
+    /// Return the pointer to the clear `value`, null if empty,
+    /// or an error if it is shorter than one texel of `format` and `type_`.
+    fn texel_value<T>(
+        format: ImageFormat,
+        type_: PixelDataType,
+        value: &[T],
+    ) -> Result<*const std::ffi::c_void, String> {
+        if value.is_empty() {
+            return Ok(std::ptr::null());
+        }
+        let expected = format.pixel_bytes(type_);
+        if std::mem::size_of_val(value) < expected {
+            return Err(format!(
+                "Clear value expects {} bytes, but got {} bytes",
+                expected,
+                std::mem::size_of_val(value)
+            ));
+        }
+        Ok(value.as_ptr() as _)
+    }
+
+    fn check_compressed(
+        format: CompressedFormat,
+        (x, y): (u32, u32),
+        (width, height, depth): (u32, u32, u32),
+        len: usize,
+    ) -> Result<(), String> {
+        let image_bytes = match format.image_bytes((width, height)) {
+            Some(bytes) => bytes,
+            None => {
+                return Err(format!(
+                    "{:?} has no fixed block size, pre-compressed data can't be uploaded",
+                    format
+                ))
+            }
+        };
+        if x % 4 != 0 || y % 4 != 0 {
+            return Err(format!(
+                "Offset ({}, {}) is not aligned to the 4x4 block of {:?}",
+                x, y, format
+            ));
+        }
+        let expected = image_bytes * depth as usize;
+        if len != expected {
+            return Err(format!(
+                "{:?} of {}x{}x{} expects {} bytes, but got {} bytes",
+                format, width, height, depth, expected, len
+            ));
+        }
+        Ok(())
+    }
+
+    /// Wrapper of `glCompressedTextureSubImage2D(...)`
+    ///
+    /// Upload pre-compressed blocks to the mipmap `level`.
+    /// The size of `data` must match the block size of `format`.
+    #[inline]
+    pub fn load_compressed(
+        &self,
+        level: u32,
+        (x, y): (u32, u32),
+        (width, height): (u32, u32),
+        format: CompressedFormat,
+        data: &[u8],
+    ) -> Result<(), String> {
+        Self::check_compressed(format, (x, y), (width, height, 1), data.len())?;
+        unsafe {
+            gl::CompressedTextureSubImage2D(
+                self.id,
+                level as _,
+                x as _,
+                y as _,
+                width as _,
+                height as _,
+                format.to_gl_format(),
+                data.len() as _,
+                data.as_ptr() as _,
+            );
+        }
+        check_error("Load Compressed Texture Error")
+    }
+
+    /// Wrapper of `glCompressedTextureSubImage3D(...)`
+    ///
+    /// Upload pre-compressed blocks to the mipmap `level` of a cube map, an array or
+    /// a 3D texture. For cube maps, `z` is the face index in the order of `TexCubeMap`.
+    #[inline]
+    pub fn load_compressed_3d(
+        &self,
+        level: u32,
+        (x, y, z): (u32, u32, u32),
+        (width, height, depth): (u32, u32, u32),
+        format: CompressedFormat,
+        data: &[u8],
+    ) -> Result<(), String> {
+        Self::check_compressed(format, (x, y), (width, height, depth), data.len())?;
+        unsafe {
+            gl::CompressedTextureSubImage3D(
+                self.id,
+                level as _,
+                x as _,
+                y as _,
+                z as _,
+                width as _,
+                height as _,
+                depth as _,
+                format.to_gl_format(),
+                data.len() as _,
+                data.as_ptr() as _,
+            );
+        }
+        check_error("Load Compressed Texture Error")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_unpacked() {
+        // A 3 texels wide RGB layer has rows of 9 bytes, uploaded with an alignment of 1.
+        let rgb = (ImageFormat::RGB, PixelDataType::u8);
+        assert!(Texture::check_unpacked((3, 2, 1), rgb.0, rgb.1, 18).is_ok());
+        assert!(Texture::check_unpacked((3, 2, 1), rgb.0, rgb.1, 17).is_err());
+        assert!(Texture::check_unpacked((3, 2, 2), rgb.0, rgb.1, 18).is_err());
+    }
+
+    #[test]
+    fn test_integer_clear_value() {
+        let (format, type_) = (ImageFormat::RGBAInteger, PixelDataType::i32);
+        assert!(Texture::texel_value(format, type_, &[1i32, 2, 3, 4]).is_ok());
+        assert!(Texture::texel_value(format, type_, &[1i32, 2, 3]).is_err());
+        assert!(Texture::texel_value(ImageFormat::RedInteger, PixelDataType::u32, &[7u32]).is_ok());
+        assert!(Texture::texel_value::<u32>(format, type_, &[])
+            .unwrap()
+            .is_null());
+    }
 }
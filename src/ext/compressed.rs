@@ -0,0 +1,501 @@
+use std::{fs, path::Path};
+
+use crate::{CompressedFormat, Context, InternalFormat, TexTarget, Texture};
+
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+const KTX2_MAGIC: &[u8; 12] = &[
+    0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+];
+
+const DDPF_ALPHAPIXELS: u32 = 0x1;
+const DDPF_FOURCC: u32 = 0x4;
+const DDSCAPS2_CUBEMAP: u32 = 0x200;
+const DDSCAPS2_CUBEMAP_ALLFACES: u32 = 0xFC00;
+const DDSCAPS2_VOLUME: u32 = 0x200000;
+const DDS_RESOURCE_MISC_TEXTURECUBE: u32 = 0x4;
+const DDS_DIMENSION_TEXTURE3D: u32 = 4;
+
+/// Pre-compressed texture data read from a DDS or KTX2 container.
+///
+/// Only block compressed formats (BC1-BC7) are supported, volume textures
+/// and supercompressed KTX2 files are rejected.
+pub struct CompressedImage {
+    format: CompressedFormat,
+    size: (u32, u32),
+    levels: u32,
+    layers: u32,
+    faces: u32,
+    array: bool,
+    data: Vec<u8>,
+    /// `(offset, len)` of every image, indexed by level, then layer, then face.
+    images: Vec<(usize, usize)>,
+}
+
+#[inline]
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, String> {
+    match bytes.get(offset..offset + 4) {
+        Some(b) => Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+        None => Err(format!("Unexpected end of file at byte {}", offset)),
+    }
+}
+
+#[inline]
+fn u64_at(bytes: &[u8], offset: usize) -> Result<u64, String> {
+    Ok(u32_at(bytes, offset)? as u64 | (u32_at(bytes, offset + 4)? as u64) << 32)
+}
+
+impl CompressedImage {
+    /// Read a DDS or KTX2 file from `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let bytes = fs::read(path.as_ref())
+            .map_err(|e| format!("Failed to read '{}': {}", path.as_ref().display(), e))?;
+        Self::parse(&bytes)
+    }
+
+    /// Parse a DDS or KTX2 container, the format is detected by its magic number.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        if bytes.starts_with(DDS_MAGIC) {
+            Self::from_dds(bytes)
+        } else if bytes.starts_with(KTX2_MAGIC) {
+            Self::from_ktx2(bytes)
+        } else {
+            Err("Unknown container, expected DDS or KTX2".to_string())
+        }
+    }
+
+    /// Parse a DDS container, including the `DX10` extended header.
+    pub fn from_dds(bytes: &[u8]) -> Result<Self, String> {
+        if !bytes.starts_with(DDS_MAGIC) {
+            return Err("Not a DDS file".to_string());
+        }
+        if u32_at(bytes, 4)? != 124 {
+            return Err("Invalid DDS header size".to_string());
+        }
+        let height = u32_at(bytes, 12)?;
+        let width = u32_at(bytes, 16)?;
+        let levels = u32_at(bytes, 28)?.max(1);
+        let pf_flags = u32_at(bytes, 80)?;
+        let caps2 = u32_at(bytes, 112)?;
+        let four_cc = &bytes[84..88];
+
+        if pf_flags & DDPF_FOURCC == 0 {
+            return Err("Uncompressed DDS files are not supported".to_string());
+        }
+        if caps2 & DDSCAPS2_VOLUME != 0 {
+            return Err("Volume textures are not supported".to_string());
+        }
+
+        let (format, layers, faces, array, mut offset) = if four_cc == b"DX10" {
+            let dxgi = u32_at(bytes, 128)?;
+            let dimension = u32_at(bytes, 132)?;
+            let misc = u32_at(bytes, 136)?;
+            let array_size = u32_at(bytes, 140)?.max(1);
+            if dimension == DDS_DIMENSION_TEXTURE3D {
+                return Err("Volume textures are not supported".to_string());
+            }
+            let format = match dxgi {
+                71 => CompressedFormat::RGBA_S3TC_DXT1,
+                72 => CompressedFormat::SRGB_ALPHA_S3TC_DXT1,
+                74 => CompressedFormat::RGBA_S3TC_DXT3,
+                75 => CompressedFormat::SRGB_ALPHA_S3TC_DXT3,
+                77 => CompressedFormat::RGBA_S3TC_DXT5,
+                78 => CompressedFormat::SRGB_ALPHA_S3TC_DXT5,
+                80 => CompressedFormat::RED_RGTC1,
+                81 => CompressedFormat::SIGNED_RED_RGTC1,
+                83 => CompressedFormat::RG_RGTC2,
+                84 => CompressedFormat::SIGNED_RG_RGTC2,
+                95 => CompressedFormat::RGB_BPTC_UNSIGNED_FLOAT,
+                96 => CompressedFormat::RGB_BPTC_SIGNED_FLOAT,
+                98 => CompressedFormat::RGBA_BPTC_UNORM,
+                99 => CompressedFormat::SRGB_ALPHA_BPTC_UNORM,
+                _ => return Err(format!("Unsupported DXGI format: {}", dxgi)),
+            };
+            let faces = if misc & DDS_RESOURCE_MISC_TEXTURECUBE != 0 {
+                6
+            } else {
+                1
+            };
+            (format, array_size, faces, array_size > 1, 148usize)
+        } else {
+            let format = match four_cc {
+                b"DXT1" if pf_flags & DDPF_ALPHAPIXELS != 0 => CompressedFormat::RGBA_S3TC_DXT1,
+                b"DXT1" => CompressedFormat::RGB_S3TC_DXT1,
+                b"DXT2" | b"DXT3" => CompressedFormat::RGBA_S3TC_DXT3,
+                b"DXT4" | b"DXT5" => CompressedFormat::RGBA_S3TC_DXT5,
+                b"ATI1" | b"BC4U" => CompressedFormat::RED_RGTC1,
+                b"BC4S" => CompressedFormat::SIGNED_RED_RGTC1,
+                b"ATI2" | b"BC5U" => CompressedFormat::RG_RGTC2,
+                b"BC5S" => CompressedFormat::SIGNED_RG_RGTC2,
+                _ => {
+                    return Err(format!(
+                        "Unsupported FourCC: {}",
+                        String::from_utf8_lossy(four_cc)
+                    ))
+                }
+            };
+            let faces = if caps2 & DDSCAPS2_CUBEMAP != 0 {
+                if caps2 & DDSCAPS2_CUBEMAP_ALLFACES != DDSCAPS2_CUBEMAP_ALLFACES {
+                    return Err("Cube maps without all six faces are not supported".to_string());
+                }
+                6
+            } else {
+                1
+            };
+            (format, 1, faces, false, 128)
+        };
+
+        let mut image = Self::empty(
+            format,
+            (width, height),
+            levels,
+            layers,
+            faces,
+            array,
+            bytes.len(),
+        )?;
+        // DDS stores every mipmap chain of a face contiguously.
+        for layer in 0..layers {
+            for face in 0..faces {
+                for level in 0..levels {
+                    let len = image.image_bytes(level);
+                    let end = offset
+                        .checked_add(len)
+                        .filter(|&end| end <= bytes.len())
+                        .ok_or("Unexpected end of file in image data")?;
+                    let index = image.index(level, layer, face);
+                    image.images[index] = (offset, len);
+                    offset = end;
+                }
+            }
+        }
+        image.data = bytes.to_vec();
+        Ok(image)
+    }
+
+    /// Parse a KTX2 container without supercompression.
+    pub fn from_ktx2(bytes: &[u8]) -> Result<Self, String> {
+        if !bytes.starts_with(KTX2_MAGIC) {
+            return Err("Not a KTX2 file".to_string());
+        }
+        let vk_format = u32_at(bytes, 12)?;
+        let width = u32_at(bytes, 20)?;
+        let height = u32_at(bytes, 24)?.max(1);
+        let depth = u32_at(bytes, 28)?;
+        let layer_count = u32_at(bytes, 32)?;
+        let faces = u32_at(bytes, 36)?;
+        let levels = u32_at(bytes, 40)?.max(1);
+        let supercompression = u32_at(bytes, 44)?;
+
+        if supercompression != 0 {
+            return Err(format!(
+                "Supercompression scheme {} is not supported",
+                supercompression
+            ));
+        }
+        if depth > 1 {
+            return Err("Volume textures are not supported".to_string());
+        }
+        if faces != 1 && faces != 6 {
+            return Err(format!("Invalid face count: {}", faces));
+        }
+        let format = match vk_format {
+            131 => CompressedFormat::RGB_S3TC_DXT1,
+            132 => CompressedFormat::SRGB_S3TC_DXT1,
+            133 => CompressedFormat::RGBA_S3TC_DXT1,
+            134 => CompressedFormat::SRGB_ALPHA_S3TC_DXT1,
+            135 => CompressedFormat::RGBA_S3TC_DXT3,
+            136 => CompressedFormat::SRGB_ALPHA_S3TC_DXT3,
+            137 => CompressedFormat::RGBA_S3TC_DXT5,
+            138 => CompressedFormat::SRGB_ALPHA_S3TC_DXT5,
+            139 => CompressedFormat::RED_RGTC1,
+            140 => CompressedFormat::SIGNED_RED_RGTC1,
+            141 => CompressedFormat::RG_RGTC2,
+            142 => CompressedFormat::SIGNED_RG_RGTC2,
+            143 => CompressedFormat::RGB_BPTC_UNSIGNED_FLOAT,
+            144 => CompressedFormat::RGB_BPTC_SIGNED_FLOAT,
+            145 => CompressedFormat::RGBA_BPTC_UNORM,
+            146 => CompressedFormat::SRGB_ALPHA_BPTC_UNORM,
+            _ => return Err(format!("Unsupported VkFormat: {}", vk_format)),
+        };
+
+        let layers = layer_count.max(1);
+        let mut image = Self::empty(
+            format,
+            (width, height),
+            levels,
+            layers,
+            faces,
+            layer_count > 0,
+            bytes.len(),
+        )?;
+        // The level index follows the 80 bytes header, each level holds all layers and faces.
+        for level in 0..levels {
+            let entry = 80 + level as usize * 24;
+            let offset = u64_at(bytes, entry)? as usize;
+            let length = u64_at(bytes, entry + 8)? as usize;
+            let len = image.image_bytes(level);
+            let required = len.checked_mul(layers as usize * faces as usize);
+            let end = offset.checked_add(length);
+            match (required, end) {
+                (Some(required), Some(end)) if required <= length && end <= bytes.len() => {}
+                _ => return Err(format!("Invalid data range of level {}", level)),
+            }
+            for layer in 0..layers {
+                for face in 0..faces {
+                    let index = image.index(level, layer, face);
+                    image.images[index] = (offset + (layer * faces + face) as usize * len, len);
+                }
+            }
+        }
+        image.data = bytes.to_vec();
+        Ok(image)
+    }
+
+    /// Validate the header fields and allocate the image table,
+    /// `file_len` bounds the number of images as each one holds at least a block.
+    fn empty(
+        format: CompressedFormat,
+        size: (u32, u32),
+        levels: u32,
+        layers: u32,
+        faces: u32,
+        array: bool,
+        file_len: usize,
+    ) -> Result<Self, String> {
+        let (width, height) = size;
+        if width == 0 || height == 0 {
+            return Err(format!("Invalid image size: {}x{}", width, height));
+        }
+        let max_levels = u32::BITS - width.max(height).leading_zeros();
+        if levels > max_levels {
+            return Err(format!(
+                "Invalid level count {} for a {}x{} image",
+                levels, width, height
+            ));
+        }
+        let count = (levels as usize)
+            .checked_mul(layers as usize)
+            .and_then(|count| count.checked_mul(faces as usize))
+            .filter(|&count| count <= file_len)
+            .ok_or_else(|| format!("Invalid image count: {} layers of {} faces", layers, faces))?;
+        Ok(Self {
+            format,
+            size,
+            levels,
+            layers,
+            faces,
+            array,
+            data: Vec::new(),
+            images: vec![(0, 0); count],
+        })
+    }
+
+    #[inline]
+    fn index(&self, level: u32, layer: u32, face: u32) -> usize {
+        (level as usize * self.layers as usize + layer as usize) * self.faces as usize
+            + face as usize
+    }
+
+    #[inline]
+    fn image_bytes(&self, level: u32) -> usize {
+        // Only block compressed formats are produced by the parsers.
+        self.format.image_bytes(self.level_size(level)).unwrap_or(0)
+    }
+
+    /// Return the compressed format of the images.
+    #[inline]
+    pub fn format(&self) -> CompressedFormat {
+        self.format
+    }
+
+    /// Return the size of the base level.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the number of mipmap levels.
+    #[inline]
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+
+    /// Return the number of array layers, `1` for non-array textures.
+    #[inline]
+    pub fn layers(&self) -> u32 {
+        self.layers
+    }
+
+    /// Return the number of faces, `6` for cube maps and `1` otherwise.
+    #[inline]
+    pub fn faces(&self) -> u32 {
+        self.faces
+    }
+
+    /// Return the size of the mipmap `level`.
+    #[inline]
+    pub fn level_size(&self, level: u32) -> (u32, u32) {
+        let shift = |size: u32| size.checked_shr(level).unwrap_or(0).max(1);
+        (shift(self.size.0), shift(self.size.1))
+    }
+
+    /// Return the compressed blocks of an image.
+    #[inline]
+    pub fn image(&self, level: u32, layer: u32, face: u32) -> Option<&[u8]> {
+        if level >= self.levels || layer >= self.layers || face >= self.faces {
+            return None;
+        }
+        let (offset, len) = self.images[self.index(level, layer, face)];
+        self.data.get(offset..offset + len)
+    }
+
+    /// Return the texture target matching the layout of the container.
+    #[inline]
+    pub fn target(&self) -> TexTarget {
+        match (self.faces, self.array) {
+            (6, true) => TexTarget::TexCubeMapArray,
+            (6, false) => TexTarget::TexCubeMap,
+            (_, true) => TexTarget::Tex2DArray,
+            _ => TexTarget::Tex2D,
+        }
+    }
+
+    /// Create a texture with immutable storage and upload every image to it.
+    pub fn upload(&self, context: &Context) -> Result<Texture, String> {
+        let target = self.target();
+        let format = InternalFormat::Compressed(self.format);
+        let texture = context.create_texture(target);
+        if self.array {
+            let (width, height) = self.size;
+            texture.storage_3d(
+                self.levels,
+                format,
+                (width, height, self.layers * self.faces),
+            )?;
+        } else {
+            texture.storage_2d(self.levels, format, self.size)?;
+        }
+        for level in 0..self.levels {
+            let (width, height) = self.level_size(level);
+            for layer in 0..self.layers {
+                for face in 0..self.faces {
+                    let data = self.image(level, layer, face).unwrap_or_default();
+                    if target == TexTarget::Tex2D {
+                        texture.load_compressed(
+                            level,
+                            (0, 0),
+                            (width, height),
+                            self.format,
+                            data,
+                        )?;
+                    } else {
+                        texture.load_compressed_3d(
+                            level,
+                            (0, 0, layer * self.faces + face),
+                            (width, height, 1),
+                            self.format,
+                            data,
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(texture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dds(four_cc: &[u8; 4], (width, height): (u32, u32), levels: u32, caps2: u32) -> Vec<u8> {
+        let mut bytes = vec![0u8; 128];
+        bytes[0..4].copy_from_slice(b"DDS ");
+        bytes[4..8].copy_from_slice(&124u32.to_le_bytes());
+        bytes[12..16].copy_from_slice(&height.to_le_bytes());
+        bytes[16..20].copy_from_slice(&width.to_le_bytes());
+        bytes[28..32].copy_from_slice(&levels.to_le_bytes());
+        bytes[80..84].copy_from_slice(&0x4u32.to_le_bytes());
+        bytes[84..88].copy_from_slice(four_cc);
+        bytes[112..116].copy_from_slice(&caps2.to_le_bytes());
+        bytes
+    }
+
+    #[test]
+    fn test_dds() {
+        let mut bytes = dds(b"DXT5", (8, 8), 2, 0);
+        bytes.extend((0..80).map(|i| i as u8));
+        let image = CompressedImage::parse(&bytes).unwrap();
+        assert_eq!(image.format(), CompressedFormat::RGBA_S3TC_DXT5);
+        assert_eq!(image.target(), TexTarget::Tex2D);
+        assert_eq!(image.levels(), 2);
+        assert_eq!(image.image(0, 0, 0).unwrap().len(), 64);
+        assert_eq!(image.image(1, 0, 0).unwrap()[0], 64);
+
+        let mut bytes = dds(b"DXT1", (4, 4), 1, 0x200 | 0xFC00);
+        bytes.extend(vec![0u8; 8 * 6]);
+        let image = CompressedImage::parse(&bytes).unwrap();
+        assert_eq!(image.target(), TexTarget::TexCubeMap);
+        assert_eq!(image.faces(), 6);
+
+        let bytes = dds(b"DXT1", (4, 4), 1, 0);
+        assert!(CompressedImage::parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_ktx2() {
+        let mut bytes = vec![
+            0xAB, 0x4B, 0x54, 0x58, 0x20, 0x32, 0x30, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
+        ];
+        for value in [145u32, 1, 8, 8, 0, 2, 1, 2, 0] {
+            bytes.extend(value.to_le_bytes());
+        }
+        bytes.resize(80, 0);
+        let data = 80 + 48;
+        for (offset, len) in [(data + 32, 128u64), (data, 32)] {
+            bytes.extend((offset as u64).to_le_bytes());
+            bytes.extend(len.to_le_bytes());
+            bytes.extend(len.to_le_bytes());
+        }
+        bytes.extend((0..160).map(|i| i as u8));
+
+        let image = CompressedImage::parse(&bytes).unwrap();
+        assert_eq!(image.format(), CompressedFormat::RGBA_BPTC_UNORM);
+        assert_eq!(image.target(), TexTarget::Tex2DArray);
+        assert_eq!(image.layers(), 2);
+        assert_eq!(image.level_size(1), (4, 4));
+        assert_eq!(image.image(0, 1, 0).unwrap()[0], 32 + 64);
+        assert_eq!(image.image(1, 1, 0).unwrap()[0], 16);
+        assert!(image.image(2, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let mut bytes = dds(b"DXT5", (8, 8), 1, 0);
+        bytes.extend(vec![0u8; 64]);
+        assert!(CompressedImage::parse(&bytes[..100]).is_err());
+
+        let mut bytes = dds(b"DXT5", (8, 8), 5, 0);
+        bytes.extend(vec![0u8; 256]);
+        assert!(CompressedImage::parse(&bytes).is_err());
+        let mut bytes = dds(b"DXT5", (8, 8), 40, 0);
+        bytes.extend(vec![0u8; 256]);
+        assert!(CompressedImage::parse(&bytes).is_err());
+
+        let mut bytes = dds(b"DXT5", (0, 8), 1, 0);
+        bytes.extend(vec![0u8; 64]);
+        assert!(CompressedImage::parse(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_level_out_of_range() {
+        let mut bytes = dds(b"DXT5", (8, 8), 4, 0);
+        bytes.extend(vec![0u8; 112]);
+        let image = CompressedImage::parse(&bytes).unwrap();
+        assert_eq!(image.level_size(3), (1, 1));
+        assert_eq!(image.level_size(40), (1, 1));
+        assert!(image.image(3, 0, 0).is_some());
+        assert!(image.image(4, 0, 0).is_none());
+        assert!(image.image(40, 0, 0).is_none());
+    }
+}
@@ -0,0 +1,423 @@
+use std::{borrow::Cow, f32::consts::PI};
+
+use crate::{
+    Cap, Context, ImageTarget, InternalFormat, MagFilter, MinFilter, PixelDataType, TexCubeMap,
+    TexParam, TexTarget, Texture, Wrap,
+};
+
+use super::ImageData;
+
+/// Cube map texture
+pub struct CubeMap {
+    texture: Texture,
+    size: u32,
+}
+
+impl CubeMap {
+    /// Create a builder of cube map.
+    #[inline]
+    pub fn builder<'a>() -> CubeMapBuilder<'a> {
+        CubeMapBuilder {
+            source: Source::Faces([None; 6]),
+            invalid_face: None,
+            internal_format: None,
+            mipmap: false,
+        }
+    }
+
+    /// Return the texture of this cube map.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Return the edge length of the faces.
+    #[inline]
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Bind this cube map to `TexTarget::TexCubeMap`.
+    #[inline]
+    pub fn bind(&self) {
+        self.texture.bind(TexTarget::TexCubeMap);
+    }
+
+    /// Take the texture out of this cube map.
+    #[inline]
+    pub fn into_texture(self) -> Texture {
+        self.texture
+    }
+}
+
+enum Source<'a> {
+    Faces([Option<ImageData<'a>>; 6]),
+    Equirectangular(ImageData<'a>, u32),
+    Cross(ImageData<'a>),
+}
+
+/// Builder of `CubeMap`.
+///
+/// The faces are taken in the order of `TexCubeMap`, which is
+/// `+X`, `-X`, `+Y`, `-Y`, `+Z`, `-Z`.
+pub struct CubeMapBuilder<'a> {
+    source: Source<'a>,
+    invalid_face: Option<ImageTarget>,
+    internal_format: Option<InternalFormat>,
+    mipmap: bool,
+}
+
+struct Face<'a> {
+    size: (u32, u32),
+    data: Cow<'a, [u8]>,
+}
+
+impl<'a> CubeMapBuilder<'a> {
+    /// Use six images as the faces.
+    #[inline]
+    pub fn faces(mut self, faces: [ImageData<'a>; 6]) -> Self {
+        self.source = Source::Faces(faces.map(Some));
+        self
+    }
+
+    /// Use `image` as the face of `target`, which must be one of `ImageTarget::TexCubeMap*`,
+    /// otherwise `build(...)` fails.
+    #[inline]
+    pub fn face(mut self, target: ImageTarget, image: ImageData<'a>) -> Self {
+        let mut faces = match self.source {
+            Source::Faces(faces) => faces,
+            _ => [None; 6],
+        };
+        match TexCubeMap::new().position(|face| face == target) {
+            Some(index) => faces[index] = Some(image),
+            None => self.invalid_face = Some(target),
+        }
+        self.source = Source::Faces(faces);
+        self
+    }
+
+    /// Slice an equirectangular (latitude-longitude) panorama into faces of `size` pixels.
+    ///
+    /// The center of the panorama looks at `-Z`.
+    #[inline]
+    pub fn equirectangular(mut self, image: ImageData<'a>, size: u32) -> Self {
+        self.source = Source::Equirectangular(image, size);
+        self
+    }
+
+    /// Slice a cross layout image into faces.
+    ///
+    /// A 4:3 image is read as a horizontal cross (`-X +Z +X -Z` in the middle row),
+    /// a 3:4 image as a vertical cross whose `-Z` face is at the bottom, upside down.
+    #[inline]
+    pub fn cross(mut self, image: ImageData<'a>) -> Self {
+        self.source = Source::Cross(image);
+        self
+    }
+
+    /// Set the internal format, which is the base format of the images by default.
+    #[inline]
+    pub fn internal_format(mut self, internal_format: InternalFormat) -> Self {
+        self.internal_format = Some(internal_format);
+        self
+    }
+
+    /// Generate mipmaps after loading the faces.
+    #[inline]
+    pub fn mipmap(mut self, mipmap: bool) -> Self {
+        self.mipmap = mipmap;
+        self
+    }
+
+    /// Build the cube map, and enable `Cap::TextureCubeMapSeamless`.
+    pub fn build(self, context: &Context) -> Result<CubeMap, String> {
+        if let Some(target) = self.invalid_face {
+            return Err(format!("{:?} is not a cube map face", target));
+        }
+        let (format, type_, faces) = match self.source {
+            Source::Faces(faces) => {
+                let mut images = Vec::with_capacity(6);
+                for (target, face) in TexCubeMap::new().zip(faces) {
+                    match face {
+                        Some(image) => images.push(image),
+                        None => return Err(format!("Missing face {:?}", target)),
+                    }
+                }
+                let (format, type_) = (images[0].format(), images[0].type_());
+                for image in &images {
+                    if image.format() != format || image.type_() != type_ {
+                        return Err(format!(
+                            "Faces have different formats: {:?}/{:?} and {:?}/{:?}",
+                            format,
+                            type_,
+                            image.format(),
+                            image.type_()
+                        ));
+                    }
+                }
+                let faces = images
+                    .into_iter()
+                    .map(|image| Face {
+                        size: image.size(),
+                        data: Cow::Borrowed(image.data()),
+                    })
+                    .collect();
+                (format, type_, faces)
+            }
+            Source::Equirectangular(image, size) => (
+                image.format(),
+                image.type_(),
+                slice_equirectangular(image, size)?,
+            ),
+            Source::Cross(image) => (image.format(), image.type_(), slice_cross(image)?),
+        };
+
+        let size = faces[0].size;
+        if size.0 == 0 {
+            return Err("Faces of 0x0 are empty".to_string());
+        }
+        for face in &faces {
+            if face.size.0 != face.size.1 {
+                return Err(format!(
+                    "Face of {}x{} is not square",
+                    face.size.0, face.size.1
+                ));
+            }
+            if face.size != size {
+                return Err(format!(
+                    "Faces have different sizes: {}x{} and {}x{}",
+                    size.0, size.1, face.size.0, face.size.1
+                ));
+            }
+        }
+
+        let internal_format = self
+            .internal_format
+            .unwrap_or(InternalFormat::Base(format.to_base_format()));
+        context.enable(Cap::TextureCubeMapSeamless);
+        let texture = context.new_texture();
+        texture.bind(TexTarget::TexCubeMap);
+        // The rows of the faces are tightly packed.
+        let alignment = context.unpack_alignment();
+        context.set_unpack_alignment(1);
+        let loaded = TexCubeMap::new()
+            .zip(&faces)
+            .try_for_each(|(target, face)| {
+                Texture::load(target, internal_format, size, format, type_, &face.data)
+            });
+        context.set_unpack_alignment(alignment);
+        loaded?;
+        texture.set(TexParam::MinFilter(if self.mipmap {
+            MinFilter::LinearMipmapLinear
+        } else {
+            MinFilter::Linear
+        }));
+        texture.set(TexParam::MagFilter(MagFilter::Linear));
+        texture.set(TexParam::WrapS(Wrap::ClampToEdge));
+        texture.set(TexParam::WrapT(Wrap::ClampToEdge));
+        texture.set(TexParam::WrapR(Wrap::ClampToEdge));
+        if self.mipmap {
+            texture.gen_minmap();
+        }
+        Ok(CubeMap {
+            texture,
+            size: size.0,
+        })
+    }
+}
+
+/// Cut the faces out of a cross layout image.
+fn slice_cross(image: ImageData) -> Result<Vec<Face<'static>>, String> {
+    let (width, height) = image.size();
+    if width == 0 || height == 0 {
+        return Err(format!("Cross image of {}x{} is empty", width, height));
+    }
+    // (column, row, upside down) of each face.
+    let layout = if width * 3 == height * 4 {
+        [
+            (2, 1, false),
+            (0, 1, false),
+            (1, 0, false),
+            (1, 2, false),
+            (1, 1, false),
+            (3, 1, false),
+        ]
+    } else if width * 4 == height * 3 {
+        [
+            (2, 1, false),
+            (0, 1, false),
+            (1, 0, false),
+            (1, 2, false),
+            (1, 1, false),
+            (1, 3, true),
+        ]
+    } else {
+        return Err(format!(
+            "Image of {}x{} is not a 4:3 or 3:4 cross layout",
+            width, height
+        ));
+    };
+    let size = width.max(height) as usize / 4;
+    let pixel = image.pixel_bytes();
+    let stride = width as usize * pixel;
+    let row_bytes = size * pixel;
+    let faces = layout
+        .iter()
+        .map(|&(column, row, flip)| {
+            let mut data = Vec::with_capacity(row_bytes * size);
+            for y in 0..size {
+                let y = if flip { size - 1 - y } else { y };
+                let start = (row * size + y) * stride + column * row_bytes;
+                let line = &image.data()[start..start + row_bytes];
+                if flip {
+                    for x in (0..size).rev() {
+                        data.extend_from_slice(&line[x * pixel..(x + 1) * pixel]);
+                    }
+                } else {
+                    data.extend_from_slice(line);
+                }
+            }
+            Face {
+                size: (size as u32, size as u32),
+                data: Cow::Owned(data),
+            }
+        })
+        .collect();
+    Ok(faces)
+}
+
+/// Resample an equirectangular panorama into six faces of `size` pixels.
+fn slice_equirectangular(image: ImageData, size: u32) -> Result<Vec<Face<'static>>, String> {
+    let (width, height) = image.size();
+    if width == 0 || height == 0 {
+        return Err(format!("Panorama of {}x{} is empty", width, height));
+    }
+    if size == 0 {
+        return Err("Faces of 0x0 are empty".to_string());
+    }
+    let pixel = image.pixel_bytes();
+    let faces = (0..6)
+        .map(|face| {
+            let mut data = vec![0u8; size as usize * size as usize * pixel];
+            for y in 0..size {
+                for x in 0..size {
+                    let u = 2.0 * (x as f32 + 0.5) / size as f32 - 1.0;
+                    let v = 2.0 * (y as f32 + 0.5) / size as f32 - 1.0;
+                    // Face orientation of the OpenGL specification.
+                    let (dx, dy, dz) = match face {
+                        0 => (1.0, -v, -u),
+                        1 => (-1.0, -v, u),
+                        2 => (u, 1.0, v),
+                        3 => (u, -1.0, -v),
+                        4 => (u, -v, 1.0),
+                        _ => (-u, -v, -1.0),
+                    };
+                    let longitude = f32::atan2(dx, -dz);
+                    let latitude = f32::atan2(dy, (dx * dx + dz * dz).sqrt());
+                    let s = 0.5 + longitude / (2.0 * PI);
+                    let t = 0.5 - latitude / PI;
+                    let offset = (y as usize * size as usize + x as usize) * pixel;
+                    sample(&image, s, t, &mut data[offset..offset + pixel]);
+                }
+            }
+            Face {
+                size: (size, size),
+                data: Cow::Owned(data),
+            }
+        })
+        .collect();
+    Ok(faces)
+}
+
+/// Sample `image` at the normalized coordinate `(s, t)`, wrapping horizontally.
+///
+/// `u8` and `f32` images are filtered bilinearly, others use the nearest pixel.
+fn sample(image: &ImageData, s: f32, t: f32, out: &mut [u8]) {
+    let (width, height) = (image.size().0 as usize, image.size().1 as usize);
+    let pixel = image.pixel_bytes();
+    let fx = (s * width as f32 - 0.5).rem_euclid(width as f32);
+    let fy = (t * height as f32 - 0.5).clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (fx as usize % width, fy as usize);
+    let (x1, y1) = ((x0 + 1) % width, (y0 + 1).min(height - 1));
+    let (ax, ay) = (fx.fract(), fy.fract());
+    let texel = |x: usize, y: usize| {
+        let offset = (y * width + x) * pixel;
+        &image.data()[offset..offset + pixel]
+    };
+
+    if image.type_().is_packed() || !matches!(image.type_(), PixelDataType::u8 | PixelDataType::f32)
+    {
+        let x = if ax < 0.5 { x0 } else { x1 };
+        let y = if ay < 0.5 { y0 } else { y1 };
+        out.copy_from_slice(texel(x, y));
+        return;
+    }
+
+    let size = image.type_().size();
+    let (p00, p10, p01, p11) = (texel(x0, y0), texel(x1, y0), texel(x0, y1), texel(x1, y1));
+    let read = |p: &[u8], i: usize| -> f32 {
+        match image.type_() {
+            PixelDataType::u8 => p[i] as f32,
+            _ => f32::from_ne_bytes([p[i * 4], p[i * 4 + 1], p[i * 4 + 2], p[i * 4 + 3]]),
+        }
+    };
+    for i in 0..pixel / size {
+        let top = read(p00, i) * (1.0 - ax) + read(p10, i) * ax;
+        let bottom = read(p01, i) * (1.0 - ax) + read(p11, i) * ax;
+        let value = top * (1.0 - ay) + bottom * ay;
+        match image.type_() {
+            PixelDataType::u8 => out[i] = value.round() as u8,
+            _ => out[i * 4..i * 4 + 4].copy_from_slice(&value.to_ne_bytes()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ImageFormat;
+
+    /// Image whose pixels are their index, as one `u8` red component.
+    fn indexed(size: (u32, u32), pixels: &[u8]) -> ImageData<'_> {
+        ImageData::new(size, ImageFormat::Red, PixelDataType::u8, pixels).unwrap()
+    }
+
+    #[test]
+    fn test_horizontal_cross() {
+        let pixels: Vec<u8> = (0..12).collect();
+        let faces = slice_cross(indexed((4, 3), &pixels)).unwrap();
+        let faces: Vec<u8> = faces.iter().map(|face| face.data[0]).collect();
+        assert_eq!(faces, [6, 4, 1, 9, 5, 7]);
+    }
+
+    #[test]
+    fn test_vertical_cross() {
+        let pixels: Vec<u8> = (0..48).collect();
+        let faces = slice_cross(indexed((6, 8), &pixels)).unwrap();
+        assert!(faces.iter().all(|face| face.size == (2, 2)));
+        assert_eq!(faces[0].data.as_ref(), [16, 17, 22, 23]);
+        // The -Z face is rotated by 180 degrees.
+        assert_eq!(faces[5].data.as_ref(), [45, 44, 39, 38]);
+    }
+
+    #[test]
+    fn test_invalid_cross() {
+        assert!(slice_cross(indexed((4, 4), &[0; 16])).is_err());
+        assert!(slice_cross(indexed((0, 0), &[])).is_err());
+    }
+
+    #[test]
+    fn test_equirectangular() {
+        // Left half 0, right half 255: -Z looks at the center, +Z at the seam.
+        let pixels: Vec<u8> = (0..32).map(|i| if i % 8 < 4 { 0 } else { 255 }).collect();
+        let faces = slice_equirectangular(indexed((8, 4), &pixels), 4).unwrap();
+        assert_eq!(faces.len(), 6);
+        assert!(faces.iter().all(|face| face.data.len() == 16));
+        // +X looks at the right quarter, -X at the left quarter.
+        assert_eq!(faces[0].data[5], 255);
+        assert_eq!(faces[1].data[5], 0);
+
+        assert!(slice_equirectangular(indexed((0, 0), &[]), 4).is_err());
+        assert!(slice_equirectangular(indexed((8, 4), &pixels), 0).is_err());
+    }
+}
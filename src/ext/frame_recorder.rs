@@ -0,0 +1,395 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{BufWriter, Write},
+    path::PathBuf,
+    sync::mpsc::{self, SyncSender},
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use crate::{
+    Buffer, Context, DefaultFrameBuffer, Fence, FrameBuffer, ImageFormat, PixelDataType, Usage,
+};
+
+/// Number of pixel pack buffers in flight before `FrameRecorder::capture(...)` blocks.
+const POOL_SIZE: usize = 3;
+
+/// Output of a `FrameRecorder`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordOutput {
+    /// A sequence of `frame_000000.png`, `frame_000001.png`, ... files in the directory.
+    PngSequence(PathBuf),
+    /// An uncompressed YUV 4:4:4 stream written to the file, with the frame rate.
+    Y4m(PathBuf, u32),
+}
+
+/// Recorder capturing every Nth frame of a frame buffer to disk.
+///
+/// The pixels are read asynchronously into a pool of `Target::PixelPack` buffers,
+/// and encoded on a background thread.
+pub struct FrameRecorder {
+    size: (u32, u32),
+    every: u32,
+    frame: u64,
+    free: Vec<Buffer>,
+    pending: VecDeque<(Buffer, Fence)>,
+    sender: Option<SyncSender<Vec<u8>>>,
+    worker: Option<JoinHandle<Result<(), String>>>,
+}
+
+impl FrameRecorder {
+    /// Create a recorder of `size` pixels capturing one frame out of `every` frames.
+    pub fn new(
+        context: &Context,
+        output: RecordOutput,
+        size: (u32, u32),
+        every: u32,
+    ) -> Result<Self, String> {
+        if every == 0 {
+            return Err("The capture interval must be at least 1 frame".to_string());
+        }
+        if size.0 == 0 || size.1 == 0 {
+            return Err(format!("Frames of {}x{} are empty", size.0, size.1));
+        }
+        let mut writer = Writer::new(output, size)?;
+        // Bounded like the GPU side, `capture(...)` blocks when the encoder falls behind.
+        let (sender, receiver) = mpsc::sync_channel::<Vec<u8>>(POOL_SIZE);
+        let worker = std::thread::spawn(move || {
+            for pixels in receiver {
+                writer.write(&pixels)?;
+            }
+            writer.finish()
+        });
+        let free = (0..POOL_SIZE)
+            .map(|_| {
+                let buffer = unsafe { context.new_buffer() };
+                buffer.allocate(frame_bytes(size), Usage::StreamRead);
+                buffer
+            })
+            .collect();
+        Ok(Self {
+            size,
+            every,
+            frame: 0,
+            free,
+            pending: VecDeque::with_capacity(POOL_SIZE),
+            sender: Some(sender),
+            worker: Some(worker),
+        })
+    }
+
+    /// Call this once per frame, after rendering to `frame_buffer`,
+    /// or to the window surface when `None`.
+    ///
+    /// The lower left part of `size` pixels is captured on every Nth call.
+    /// The caller must keep `size` within the surface of `frame_buffer`,
+    /// pixels outside of it are undefined and recorded as is.
+    pub fn capture(
+        &mut self,
+        context: &Context,
+        frame_buffer: Option<&FrameBuffer>,
+    ) -> Result<(), String> {
+        let capture = self.frame.is_multiple_of(self.every as u64);
+        self.frame += 1;
+        self.poll(false)?;
+        if !capture {
+            return Ok(());
+        }
+        if self.free.is_empty() {
+            self.poll(true)?;
+        }
+        let buffer = match self.free.pop() {
+            Some(buffer) => buffer,
+            None => return Ok(()),
+        };
+        let rect = ((0, 0), self.size);
+        let (format, type_) = (ImageFormat::RGBA, PixelDataType::u8);
+        let read = match frame_buffer {
+            Some(frame_buffer) => frame_buffer.read_pixels_into(&buffer, rect, format, type_),
+            None => DefaultFrameBuffer.read_pixels_into(&buffer, rect, format, type_),
+        };
+        if let Err(err) = read {
+            self.free.push(buffer);
+            return Err(err);
+        }
+        self.pending.push_back((buffer, context.fence()));
+        Ok(())
+    }
+
+    /// Flush the frames in flight, and wait for the files to be written.
+    pub fn finish(mut self) -> Result<(), String> {
+        while !self.pending.is_empty() {
+            self.poll(true)?;
+        }
+        self.sender.take();
+        match self.worker.take() {
+            Some(worker) => worker
+                .join()
+                .map_err(|_| "Frame recorder thread panicked".to_string())?,
+            None => Ok(()),
+        }
+    }
+
+    /// Return the number of frames seen by `capture(...)`.
+    #[inline]
+    pub fn frames(&self) -> u64 {
+        self.frame
+    }
+
+    /// Return the size of the captured frames.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Send the frames that have arrived to the worker, waiting for the oldest if `block`.
+    fn poll(&mut self, mut block: bool) -> Result<(), String> {
+        while let Some((_, fence)) = self.pending.front() {
+            let ready = if block {
+                fence.wait(Duration::MAX)
+            } else {
+                fence.is_signaled()
+            };
+            if !ready {
+                break;
+            }
+            let (buffer, _) = self.pending.pop_front().unwrap();
+            let mut pixels = vec![0u8; frame_bytes(self.size)];
+            buffer.get_sub_data(0, &mut pixels);
+            self.free.push(buffer);
+            flip_rows(&mut pixels, self.size.0 as usize * 4);
+            let sender = self.sender.as_ref().ok_or("Frame recorder is finished")?;
+            if sender.send(pixels).is_err() {
+                self.sender.take();
+                return match self.worker.take().map(|worker| worker.join()) {
+                    Some(Ok(Err(err))) => Err(err),
+                    _ => Err("Frame recorder thread stopped".to_string()),
+                };
+            }
+            block = false;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for FrameRecorder {
+    fn drop(&mut self) {
+        self.sender.take();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+#[inline]
+fn frame_bytes((width, height): (u32, u32)) -> usize {
+    width as usize * height as usize * 4
+}
+
+/// Turn bottom-up rows of `stride` bytes into top-down rows.
+fn flip_rows(pixels: &mut [u8], stride: usize) {
+    let rows = pixels.len() / stride;
+    for y in 0..rows / 2 {
+        let (top, bottom) = pixels.split_at_mut((rows - 1 - y) * stride);
+        top[y * stride..(y + 1) * stride].swap_with_slice(&mut bottom[..stride]);
+    }
+}
+
+enum Writer {
+    Png {
+        directory: PathBuf,
+        size: (u32, u32),
+        index: u64,
+    },
+    Y4m {
+        file: BufWriter<File>,
+        size: (u32, u32),
+    },
+}
+
+impl Writer {
+    fn new(output: RecordOutput, size: (u32, u32)) -> Result<Self, String> {
+        match output {
+            RecordOutput::PngSequence(directory) => {
+                std::fs::create_dir_all(&directory).map_err(|err| err.to_string())?;
+                Ok(Writer::Png {
+                    directory,
+                    size,
+                    index: 0,
+                })
+            }
+            RecordOutput::Y4m(path, fps) => {
+                let mut file = BufWriter::new(File::create(path).map_err(|err| err.to_string())?);
+                writeln!(
+                    file,
+                    "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444",
+                    size.0,
+                    size.1,
+                    fps.max(1)
+                )
+                .map_err(|err| err.to_string())?;
+                Ok(Writer::Y4m { file, size })
+            }
+        }
+    }
+
+    /// Write one frame of top-down RGBA pixels.
+    fn write(&mut self, pixels: &[u8]) -> Result<(), String> {
+        match self {
+            Writer::Png {
+                directory,
+                size,
+                index,
+            } => {
+                let path = directory.join(format!("frame_{:06}.png", index));
+                *index += 1;
+                std::fs::write(path, encode_png(*size, pixels)).map_err(|err| err.to_string())
+            }
+            Writer::Y4m { file, size } => {
+                let count = size.0 as usize * size.1 as usize;
+                let mut planes = vec![0u8; count * 3];
+                for (i, pixel) in pixels.chunks_exact(4).enumerate() {
+                    let (r, g, b) = (pixel[0] as i32, pixel[1] as i32, pixel[2] as i32);
+                    // BT.601 studio range.
+                    planes[i] = (((66 * r + 129 * g + 25 * b + 128) >> 8) + 16) as u8;
+                    planes[count + i] = (((-38 * r - 74 * g + 112 * b + 128) >> 8) + 128) as u8;
+                    planes[count * 2 + i] = (((112 * r - 94 * g - 18 * b + 128) >> 8) + 128) as u8;
+                }
+                file.write_all(b"FRAME\n")
+                    .and_then(|_| file.write_all(&planes))
+                    .map_err(|err| err.to_string())
+            }
+        }
+    }
+
+    fn finish(self) -> Result<(), String> {
+        match self {
+            Writer::Png { .. } => Ok(()),
+            Writer::Y4m { mut file, .. } => file.flush().map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Encode top-down RGBA pixels as a PNG with uncompressed deflate blocks.
+fn encode_png((width, height): (u32, u32), pixels: &[u8]) -> Vec<u8> {
+    let stride = width as usize * 4;
+    let mut raw = Vec::with_capacity((stride + 1) * height as usize);
+    for row in pixels.chunks_exact(stride) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut zlib = vec![0x78, 0x01];
+    let mut blocks = raw.chunks(0xFFFF).peekable();
+    if blocks.peek().is_none() {
+        zlib.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        zlib.push(blocks.peek().is_none() as u8);
+        zlib.extend_from_slice(&len.to_le_bytes());
+        zlib.extend_from_slice(&(!len).to_le_bytes());
+        zlib.extend_from_slice(block);
+    }
+    zlib.extend_from_slice(&adler32(&raw).to_be_bytes());
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&width.to_be_bytes());
+    header.extend_from_slice(&height.to_be_bytes());
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    write_chunk(&mut png, b"IHDR", &header);
+    write_chunk(&mut png, b"IDAT", &zlib);
+    write_chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+fn write_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        assert_eq!(adler32(&[]), 1);
+    }
+
+    #[test]
+    fn test_flip_rows() {
+        let mut even = [0, 0, 1, 1, 2, 2, 3, 3];
+        flip_rows(&mut even, 2);
+        assert_eq!(even, [3, 3, 2, 2, 1, 1, 0, 0]);
+        let mut odd = [0, 0, 1, 1, 2, 2];
+        flip_rows(&mut odd, 2);
+        assert_eq!(odd, [2, 2, 1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_png_stored_blocks() {
+        // 128 rows of 128 pixels are 65664 bytes with the filter bytes, two blocks.
+        let size = (128, 128);
+        let pixels = vec![7u8; frame_bytes(size)];
+        let png = encode_png(size, &pixels);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+
+        let idat = 8 + 12 + 13;
+        let len = u32::from_be_bytes(png[idat..idat + 4].try_into().unwrap()) as usize;
+        assert_eq!(&png[idat + 4..idat + 8], b"IDAT");
+        let zlib = &png[idat + 8..idat + 8 + len];
+        let raw_len = (128 * 4 + 1) * 128;
+        assert_eq!(zlib.len(), 2 + 5 * 2 + raw_len + 4);
+
+        let first = &zlib[2..7];
+        assert_eq!(first, [0, 0xFF, 0xFF, 0x00, 0x00]);
+        let second = &zlib[7 + 0xFFFF..12 + 0xFFFF];
+        let rest = (raw_len - 0xFFFF) as u16;
+        assert_eq!(second[0], 1);
+        assert_eq!(&second[1..3], rest.to_le_bytes());
+        assert_eq!(&second[3..5], (!rest).to_le_bytes());
+
+        let crc = &png[idat + 8 + len..idat + 12 + len];
+        assert_eq!(crc, crc32(&png[idat + 4..idat + 8 + len]).to_be_bytes());
+        assert_eq!(&png[png.len() - 8..png.len() - 4], b"IEND");
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{ImageFormat, PixelDataType};
+
+/// Borrowed pixel data together with its layout, rows are tightly packed.
+#[derive(Debug, Clone, Copy)]
+pub struct ImageData<'a> {
+    size: (u32, u32),
+    format: ImageFormat,
+    type_: PixelDataType,
+    data: &'a [u8],
+}
+
+impl<'a> ImageData<'a> {
+    /// Describe `data` as an image of `size` pixels.
+    ///
+    /// It will return `Err(String)` if `data` is smaller than the image.
+    #[inline]
+    pub fn new<T: Copy>(
+        size: (u32, u32),
+        format: ImageFormat,
+        type_: PixelDataType,
+        data: &'a [T],
+    ) -> Result<Self, String> {
+        let data = unsafe {
+            std::slice::from_raw_parts(data.as_ptr() as *const u8, std::mem::size_of_val(data))
+        };
+        let expected = size.0 as usize * size.1 as usize * format.pixel_bytes(type_);
+        if data.len() < expected {
+            return Err(format!(
+                "Image of {}x{} {:?}/{:?} expects {} bytes, but got {} bytes",
+                size.0,
+                size.1,
+                format,
+                type_,
+                expected,
+                data.len()
+            ));
+        }
+        Ok(Self {
+            size,
+            format,
+            type_,
+            data,
+        })
+    }
+
+    /// Return the size of the image.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the pixel format of the image.
+    #[inline]
+    pub fn format(&self) -> ImageFormat {
+        self.format
+    }
+
+    /// Return the pixel data type of the image.
+    #[inline]
+    pub fn type_(&self) -> PixelDataType {
+        self.type_
+    }
+
+    /// Return the raw bytes of the image.
+    #[inline]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Return the byte size of one pixel.
+    #[inline]
+    pub fn pixel_bytes(&self) -> usize {
+        self.format.pixel_bytes(self.type_)
+    }
+}
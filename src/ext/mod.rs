@@ -1,6 +1,24 @@
 //! Here contains the basic encapsulation of OpenGL content,
 //! which are not APIs directly exposed by OpenGL.
 
+mod compressed;
+mod cube_map;
+mod frame_recorder;
+mod image;
+mod post_chain;
+mod render_pass;
+mod render_target;
+mod render_target_pool;
+mod texture_array;
 mod vertex;
 
+pub use compressed::CompressedImage;
+pub use cube_map::{CubeMap, CubeMapBuilder};
+pub use frame_recorder::{FrameRecorder, RecordOutput};
+pub use image::ImageData;
+pub use post_chain::{PostChain, PostPass, POST_VERTEX_SHADER};
+pub use render_pass::{LoadOp, PassDesc, RenderPass, StoreOp};
+pub use render_target::{RenderTarget, RenderTargetBuilder, RenderTargetError};
+pub use render_target_pool::{RenderTargetKey, RenderTargetLease, RenderTargetPool};
+pub use texture_array::{Texture3D, TextureArray};
 pub use vertex::Vertex;
@@ -0,0 +1,295 @@
+use crate::{
+    Buffer, Context, DefaultFrameBuffer, FrameBuffer, FrameBufferTarget, GlType, Mode, Program,
+    ShaderType, SizedFormat, Target, TexTarget, Texture, Usage,
+};
+
+use super::{RenderTarget, Vertex};
+
+/// Vertex shader of the fullscreen triangle, passing `v_uv` to the passes.
+pub const POST_VERTEX_SHADER: &str = r#"#version 450 core
+layout(location = 0) in vec2 a_position;
+out vec2 v_uv;
+
+void main() {
+    v_uv = a_position * 0.5 + 0.5;
+    gl_Position = vec4(a_position, 0.0, 1.0);
+}
+"#;
+
+const TONEMAP_SHADER: &str = r#"#version 450 core
+uniform sampler2D u_input;
+uniform float u_exposure;
+in vec2 v_uv;
+out vec4 o_color;
+
+void main() {
+    vec3 color = texture(u_input, v_uv).rgb * u_exposure;
+    // ACES filmic curve fitted by Krzysztof Narkowicz.
+    color = clamp((color * (2.51 * color + 0.03)) / (color * (2.43 * color + 0.59) + 0.14), 0.0, 1.0);
+    o_color = vec4(pow(color, vec3(1.0 / 2.2)), 1.0);
+}
+"#;
+
+const FXAA_SHADER: &str = r#"#version 450 core
+uniform sampler2D u_input;
+in vec2 v_uv;
+out vec4 o_color;
+
+float luma(vec3 color) {
+    return dot(color, vec3(0.299, 0.587, 0.114));
+}
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(u_input, 0));
+    vec4 center = texture(u_input, v_uv);
+    float luma_nw = luma(texture(u_input, v_uv + vec2(-1.0, -1.0) * texel).rgb);
+    float luma_ne = luma(texture(u_input, v_uv + vec2(1.0, -1.0) * texel).rgb);
+    float luma_sw = luma(texture(u_input, v_uv + vec2(-1.0, 1.0) * texel).rgb);
+    float luma_se = luma(texture(u_input, v_uv + vec2(1.0, 1.0) * texel).rgb);
+    float luma_m = luma(center.rgb);
+    float luma_min = min(luma_m, min(min(luma_nw, luma_ne), min(luma_sw, luma_se)));
+    float luma_max = max(luma_m, max(max(luma_nw, luma_ne), max(luma_sw, luma_se)));
+
+    vec2 dir = vec2(-((luma_nw + luma_ne) - (luma_sw + luma_se)), (luma_nw + luma_sw) - (luma_ne + luma_se));
+    float reduce = max((luma_nw + luma_ne + luma_sw + luma_se) * (0.25 / 8.0), 1.0 / 128.0);
+    float scale = 1.0 / (min(abs(dir.x), abs(dir.y)) + reduce);
+    dir = clamp(dir * scale, vec2(-8.0), vec2(8.0)) * texel;
+
+    vec3 a = 0.5 * (texture(u_input, v_uv + dir * (1.0 / 3.0 - 0.5)).rgb
+        + texture(u_input, v_uv + dir * (2.0 / 3.0 - 0.5)).rgb);
+    vec3 b = a * 0.5 + 0.25 * (texture(u_input, v_uv - dir * 0.5).rgb
+        + texture(u_input, v_uv + dir * 0.5).rgb);
+    float luma_b = luma(b);
+    o_color = vec4(luma_b < luma_min || luma_b > luma_max ? a : b, center.a);
+}
+"#;
+
+const GAUSSIAN_BLUR_SHADER: &str = r#"#version 450 core
+uniform sampler2D u_input;
+uniform int u_horizontal;
+in vec2 v_uv;
+out vec4 o_color;
+
+const float WEIGHTS[5] = float[](0.227027, 0.1945946, 0.1216216, 0.054054, 0.016216);
+
+void main() {
+    vec2 texel = 1.0 / vec2(textureSize(u_input, 0));
+    vec2 offset = u_horizontal != 0 ? vec2(texel.x, 0.0) : vec2(0.0, texel.y);
+    vec4 color = texture(u_input, v_uv) * WEIGHTS[0];
+    for (int i = 1; i < 5; i++) {
+        color += texture(u_input, v_uv + offset * float(i)) * WEIGHTS[i];
+        color += texture(u_input, v_uv - offset * float(i)) * WEIGHTS[i];
+    }
+    o_color = color;
+}
+"#;
+
+type SetUniforms = Box<dyn FnMut(&Context, &Program) -> Result<(), String>>;
+
+/// Full-screen pass of a `PostChain`.
+///
+/// The fragment shader reads the previous pass from `sampler2D u_input` on texture unit 0,
+/// and the input of the chain from `sampler2D u_source` on texture unit 1.
+pub struct PostPass {
+    program: Program,
+    uniforms: Option<SetUniforms>,
+    label: String,
+}
+
+impl PostPass {
+    /// Create a pass running `program`, which should use `POST_VERTEX_SHADER`.
+    #[inline]
+    pub fn new(program: Program) -> Self {
+        Self {
+            program,
+            uniforms: None,
+            label: String::from("Post Pass"),
+        }
+    }
+
+    /// Compile a pass from the source code of a fragment shader.
+    pub fn from_fragment(context: &Context, fragment: &str) -> Result<Self, String> {
+        let vertex_shader = context.new_shader(ShaderType::Vertex);
+        vertex_shader.source(POST_VERTEX_SHADER);
+        vertex_shader.compile()?;
+        let fragment_shader = context.new_shader(ShaderType::Fragmet);
+        fragment_shader.source(fragment);
+        fragment_shader.compile()?;
+        let program = context.new_program();
+        program.attach(&vertex_shader);
+        program.attach(&fragment_shader);
+        program.link()?;
+        Ok(Self::new(program))
+    }
+
+    /// Set the function called before drawing, with the program in use,
+    /// to set uniforms or bind extra textures to units other than 0 and 1.
+    #[inline]
+    pub fn uniforms<F>(mut self, uniforms: F) -> Self
+    where
+        F: FnMut(&Context, &Program) -> Result<(), String> + 'static,
+    {
+        self.uniforms = Some(Box::new(uniforms));
+        self
+    }
+
+    /// Set the name of the debug group wrapping the pass.
+    #[inline]
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    /// Return the program of this pass.
+    #[inline]
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+}
+
+/// Stack of full-screen passes ping-ponging between two render targets of the same size.
+///
+/// The passes draw with the current depth, blend and stencil state,
+/// which should usually be disabled.
+pub struct PostChain {
+    targets: [RenderTarget; 2],
+    triangle: Vertex,
+    passes: Vec<PostPass>,
+    format: SizedFormat,
+}
+
+impl PostChain {
+    /// Create an empty chain of `size` pixels whose intermediate targets use `format`.
+    pub fn new(context: &Context, size: (u32, u32), format: SizedFormat) -> Result<Self, String> {
+        let target = || {
+            RenderTarget::builder()
+                .size(size.0, size.1)
+                .color(format)
+                .build(context)
+                .map_err(|err| err.to_string())
+        };
+        let targets = [target()?, target()?];
+        let mut triangle = Vertex::new(context);
+        triangle.new_buffer(context, |buffer| {
+            buffer.bind(Target::Array);
+            buffer.data(&[-1.0f32, -1.0, 3.0, -1.0, -1.0, 3.0], Usage::StaticDraw);
+            Buffer::gen_mark(&[(GlType::f32, 2)]);
+        });
+        Ok(Self {
+            targets,
+            triangle,
+            passes: Vec::new(),
+            format,
+        })
+    }
+
+    /// Append `pass` to the chain.
+    #[inline]
+    pub fn push(&mut self, pass: PostPass) {
+        self.passes.push(pass);
+    }
+
+    /// Append a pass mapping HDR colors to display colors with the ACES filmic curve,
+    /// followed by gamma correction.
+    pub fn push_tonemap(&mut self, context: &Context, exposure: f32) -> Result<(), String> {
+        let pass = PostPass::from_fragment(context, TONEMAP_SHADER)?
+            .label("Tonemap")
+            .uniforms(move |_, program| program.set_uniform("u_exposure", &exposure));
+        self.push(pass);
+        Ok(())
+    }
+
+    /// Append a fast approximate anti-aliasing pass, which expects display colors.
+    pub fn push_fxaa(&mut self, context: &Context) -> Result<(), String> {
+        self.push(PostPass::from_fragment(context, FXAA_SHADER)?.label("FXAA"));
+        Ok(())
+    }
+
+    /// Append a separable 9-tap gaussian blur, as a horizontal and a vertical pass.
+    pub fn push_gaussian_blur(&mut self, context: &Context) -> Result<(), String> {
+        for (horizontal, label) in [(1, "Gaussian Blur X"), (0, "Gaussian Blur Y")] {
+            let pass = PostPass::from_fragment(context, GAUSSIAN_BLUR_SHADER)?
+                .label(label)
+                .uniforms(move |_, program| program.set_uniform("u_horizontal", &horizontal));
+            self.push(pass);
+        }
+        Ok(())
+    }
+
+    /// Run all passes on `source`, the last one writing to `output`,
+    /// or to the window surface when `None`. Without passes, `source` is not copied.
+    pub fn run(
+        &mut self,
+        context: &Context,
+        source: &Texture,
+        output: Option<&FrameBuffer>,
+    ) -> Result<(), String> {
+        let size = self.size();
+        let count = self.passes.len();
+        for (index, pass) in self.passes.iter_mut().enumerate() {
+            context.push_debug_group(&pass.label);
+            let input = match index {
+                0 => source,
+                _ => self.targets[(index - 1) % 2].color(0).unwrap(),
+            };
+            let frame_buffer = if index + 1 == count {
+                output
+            } else {
+                Some(self.targets[index % 2].frame_buffer())
+            };
+            let _binding = match frame_buffer {
+                Some(frame_buffer) => frame_buffer.bind_scoped(FrameBufferTarget::Draw),
+                None => DefaultFrameBuffer.bind_scoped(FrameBufferTarget::Draw),
+            };
+            context.view_port(0, 0, size.0 as _, size.1 as _);
+            Texture::active(0);
+            input.bind(TexTarget::Tex2D);
+            Texture::active(1);
+            source.bind(TexTarget::Tex2D);
+            Texture::active(0);
+            pass.program.using();
+            // The built-in names are optional, unused uniforms are optimized away.
+            let _ = pass.program.set_uniform("u_input", &0i32);
+            let _ = pass.program.set_uniform("u_source", &1i32);
+            let result = match &mut pass.uniforms {
+                Some(uniforms) => uniforms(context, &pass.program),
+                None => Ok(()),
+            };
+            if result.is_ok() {
+                self.triangle.using();
+                context.draw_arrays(Mode::Triangles, 0, 3);
+            }
+            context.pop_debug_group();
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Reallocate the intermediate targets with the new size.
+    pub fn resize(&mut self, context: &Context, width: u32, height: u32) -> Result<(), String> {
+        for target in &mut self.targets {
+            target
+                .resize(context, width, height)
+                .map_err(|err| err.to_string())?;
+        }
+        Ok(())
+    }
+
+    /// Return the passes of the chain.
+    #[inline]
+    pub fn passes(&self) -> &[PostPass] {
+        &self.passes
+    }
+
+    /// Return the size of the intermediate targets.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.targets[0].size()
+    }
+
+    /// Return the format of the intermediate targets.
+    #[inline]
+    pub fn format(&self) -> SizedFormat {
+        self.format
+    }
+}
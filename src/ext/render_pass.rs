@@ -0,0 +1,187 @@
+use std::ops::Deref;
+
+use crate::{Cap, Context, FrameBufferAttachment, FrameBufferBinding, FrameBufferTarget};
+
+use super::RenderTarget;
+
+/// What happens to an attachment at the beginning of a pass.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LoadOp<T> {
+    /// Clear the attachment to the value.
+    Clear(T),
+    /// Keep the previous contents.
+    #[default]
+    Load,
+    /// The previous contents are not needed, they are invalidated.
+    DontCare,
+}
+
+/// What happens to an attachment at the end of a pass.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StoreOp {
+    /// Keep the rendered contents.
+    #[default]
+    Store,
+    /// The rendered contents are not needed after the pass, they are invalidated.
+    DontCare,
+}
+
+/// Description of a `RenderPass`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PassDesc<'a> {
+    /// Load operation of all color attachments.
+    pub color_load: LoadOp<[f32; 4]>,
+    /// Load operation of the depth buffer.
+    pub depth_load: LoadOp<f32>,
+    /// Load operation of the stencil buffer.
+    pub stencil_load: LoadOp<i32>,
+    /// Store operation of all color attachments.
+    pub color_store: StoreOp,
+    /// Store operation of the depth and stencil buffers.
+    pub depth_stencil_store: StoreOp,
+    /// `((x, y), (width, height))` of the viewport, the whole target by default.
+    pub viewport: Option<((i32, i32), (u32, u32))>,
+    /// Name of the debug group wrapping the pass.
+    pub label: Option<&'a str>,
+}
+
+/// Pass rendering to a `RenderTarget`, created by `Context::begin_pass(...)`.
+///
+/// Draws are issued through the context it dereferences to. On drop, the store operations
+/// are applied and the previous frame buffers and viewport are restored.
+pub struct RenderPass<'a> {
+    context: &'a Context,
+    target: &'a RenderTarget,
+    color_store: StoreOp,
+    depth_stencil_store: StoreOp,
+    labeled: bool,
+    _binding: FrameBufferBinding<'a>,
+}
+
+impl Context {
+    /// Begin a pass rendering to `target`, applying the load operations of `desc`.
+    ///
+    /// The clears ignore the current write masks and scissor test, which are left unchanged.
+    pub fn begin_pass<'a>(
+        &'a self,
+        target: &'a RenderTarget,
+        desc: PassDesc,
+    ) -> Result<RenderPass<'a>, String> {
+        if let Some(label) = desc.label {
+            self.push_debug_group(label);
+        }
+        let frame_buffer = target.frame_buffer();
+        let binding = frame_buffer.bind_scoped(FrameBufferTarget::Draw);
+        let ((x, y), (width, height)) = desc.viewport.unwrap_or(((0, 0), target.size()));
+        self.view_port(x, y, width as _, height as _);
+        let pass = RenderPass {
+            context: self,
+            target,
+            color_store: desc.color_store,
+            depth_stencil_store: desc.depth_stencil_store,
+            labeled: desc.label.is_some(),
+            _binding: binding,
+        };
+
+        // The clears write whole attachments whatever the state left by previous passes.
+        let _scissor = self.scoped_disable(Cap::ScissorTest);
+
+        match desc.color_load {
+            LoadOp::Clear(color) => {
+                let _mask = self.scoped_color_mask(true, true, true, true);
+                for index in 0..target.colors().len() {
+                    frame_buffer.clear_color_f(index as _, color);
+                }
+            }
+            LoadOp::Load => {}
+            LoadOp::DontCare => frame_buffer.invalidate(&color_attachments(target))?,
+        }
+
+        let depth_stencil = target
+            .depth_stencil_format()
+            .and_then(|format| format.depth_stencil_attachment());
+        let (has_depth, has_stencil) = match depth_stencil {
+            Some(FrameBufferAttachment::Depth) => (true, false),
+            Some(FrameBufferAttachment::Stencil) => (false, true),
+            Some(_) => (true, true),
+            None => (false, false),
+        };
+        let mut invalidated = Vec::new();
+        let _depth_mask =
+            matches!(desc.depth_load, LoadOp::Clear(_)).then(|| self.scoped_depth_mask(true));
+        let _stencil_mask =
+            matches!(desc.stencil_load, LoadOp::Clear(_)).then(|| self.scoped_stencil_mask(!0));
+        match (has_depth, desc.depth_load, has_stencil, desc.stencil_load) {
+            (true, LoadOp::Clear(depth), true, LoadOp::Clear(stencil)) => {
+                frame_buffer.clear_depth_stencil(depth, stencil);
+            }
+            _ => {
+                if has_depth {
+                    match desc.depth_load {
+                        LoadOp::Clear(depth) => frame_buffer.clear_depth(depth),
+                        LoadOp::Load => {}
+                        LoadOp::DontCare => invalidated.push(FrameBufferAttachment::Depth),
+                    }
+                }
+                if has_stencil {
+                    match desc.stencil_load {
+                        LoadOp::Clear(stencil) => frame_buffer.clear_stencil(stencil),
+                        LoadOp::Load => {}
+                        LoadOp::DontCare => invalidated.push(FrameBufferAttachment::Stencil),
+                    }
+                }
+            }
+        }
+        if !invalidated.is_empty() {
+            frame_buffer.invalidate(&invalidated)?;
+        }
+        Ok(pass)
+    }
+}
+
+impl RenderPass<'_> {
+    /// Return the render target of this pass.
+    #[inline]
+    pub fn target(&self) -> &RenderTarget {
+        self.target
+    }
+}
+
+impl Deref for RenderPass<'_> {
+    type Target = Context;
+
+    #[inline]
+    fn deref(&self) -> &Context {
+        self.context
+    }
+}
+
+impl Drop for RenderPass<'_> {
+    fn drop(&mut self) {
+        let mut invalidated = Vec::new();
+        if self.color_store == StoreOp::DontCare {
+            invalidated.extend(color_attachments(self.target));
+        }
+        if self.depth_stencil_store == StoreOp::DontCare {
+            if let Some(attachment) = self
+                .target
+                .depth_stencil_format()
+                .and_then(|format| format.depth_stencil_attachment())
+            {
+                invalidated.push(attachment);
+            }
+        }
+        if !invalidated.is_empty() {
+            let _ = self.target.frame_buffer().invalidate(&invalidated);
+        }
+        if self.labeled {
+            self.context.pop_debug_group();
+        }
+    }
+}
+
+fn color_attachments(target: &RenderTarget) -> Vec<FrameBufferAttachment> {
+    (0..target.colors().len() as u32)
+        .map(FrameBufferAttachment::Color)
+        .collect()
+}
@@ -0,0 +1,265 @@
+use crate::{
+    Context, FrameBuffer, FrameBufferAttachment, FrameBufferError, FrameBufferTarget,
+    InternalFormat, MagFilter, MinFilter, RenderBuffer, RenderBufferFormat, SizedFormat, TexParam,
+    TexTarget, Texture, Wrap,
+};
+
+/// Error of building or resizing a `RenderTarget`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenderTargetError {
+    /// An attachment could not be created.
+    Invalid(String),
+    /// The attachments do not form a complete frame buffer.
+    Incomplete(FrameBufferError),
+}
+
+impl std::fmt::Display for RenderTargetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderTargetError::Invalid(err) => write!(f, "{}", err),
+            RenderTargetError::Incomplete(err) => {
+                write!(f, "Render target is not complete: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for RenderTargetError {}
+
+impl From<String> for RenderTargetError {
+    #[inline]
+    fn from(err: String) -> Self {
+        RenderTargetError::Invalid(err)
+    }
+}
+
+impl From<FrameBufferError> for RenderTargetError {
+    #[inline]
+    fn from(err: FrameBufferError) -> Self {
+        RenderTargetError::Incomplete(err)
+    }
+}
+
+/// Offscreen frame buffer owning its attachments.
+///
+/// Color attachment `i` is a texture written by fragment output `i`,
+/// the depth and stencil buffers are a render buffer.
+pub struct RenderTarget {
+    frame_buffer: FrameBuffer,
+    colors: Vec<Texture>,
+    depth_stencil: Option<RenderBuffer>,
+    color_formats: Vec<SizedFormat>,
+    depth_stencil_format: Option<RenderBufferFormat>,
+    size: (u32, u32),
+    samples: u32,
+}
+
+/// Builder of `RenderTarget`.
+pub struct RenderTargetBuilder {
+    size: (u32, u32),
+    colors: Vec<SizedFormat>,
+    depth_stencil: Option<RenderBufferFormat>,
+    samples: u32,
+}
+
+impl RenderTarget {
+    /// Create a builder of render target.
+    #[inline]
+    pub fn builder() -> RenderTargetBuilder {
+        RenderTargetBuilder {
+            size: (1, 1),
+            colors: Vec::new(),
+            depth_stencil: None,
+            samples: 0,
+        }
+    }
+
+    /// Reallocate all attachments with the new size, their contents are lost.
+    pub fn resize(
+        &mut self,
+        context: &Context,
+        width: u32,
+        height: u32,
+    ) -> Result<(), RenderTargetError> {
+        if (width, height) == self.size {
+            return Ok(());
+        }
+        let frame_buffer = context.new_frame_buffer();
+        let (colors, depth_stencil) = attach(
+            context,
+            &frame_buffer,
+            &self.color_formats,
+            self.depth_stencil_format,
+            self.samples,
+            (width, height),
+        )?;
+        self.frame_buffer = frame_buffer;
+        self.colors = colors;
+        self.depth_stencil = depth_stencil;
+        self.size = (width, height);
+        Ok(())
+    }
+
+    /// Bind the frame buffer to `FrameBufferTarget::Draw`, and set the viewport to its size.
+    #[inline]
+    pub fn bind(&self, context: &Context) {
+        self.frame_buffer.bind(FrameBufferTarget::Draw);
+        context.view_port(0, 0, self.size.0 as _, self.size.1 as _);
+    }
+
+    /// Return the frame buffer.
+    #[inline]
+    pub fn frame_buffer(&self) -> &FrameBuffer {
+        &self.frame_buffer
+    }
+
+    /// Return the texture of color attachment `index`.
+    #[inline]
+    pub fn color(&self, index: usize) -> Option<&Texture> {
+        self.colors.get(index)
+    }
+
+    /// Return the textures of all color attachments.
+    #[inline]
+    pub fn colors(&self) -> &[Texture] {
+        &self.colors
+    }
+
+    /// Return the formats of all color attachments.
+    #[inline]
+    pub fn color_formats(&self) -> &[SizedFormat] {
+        &self.color_formats
+    }
+
+    /// Return the depth and stencil render buffer.
+    #[inline]
+    pub fn depth_stencil(&self) -> Option<&RenderBuffer> {
+        self.depth_stencil.as_ref()
+    }
+
+    /// Return the format of the depth and stencil render buffer.
+    #[inline]
+    pub fn depth_stencil_format(&self) -> Option<RenderBufferFormat> {
+        self.depth_stencil_format
+    }
+
+    /// Return the size of the attachments.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the number of samples, `0` if it is not multisampled.
+    #[inline]
+    pub fn samples(&self) -> u32 {
+        self.samples
+    }
+}
+
+impl RenderTargetBuilder {
+    /// Set the size of the attachments.
+    #[inline]
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// Add a color attachment, in the order of fragment outputs.
+    #[inline]
+    pub fn color(mut self, format: SizedFormat) -> Self {
+        self.colors.push(format);
+        self
+    }
+
+    /// Set the depth and stencil attachment, which must be a depth or stencil format.
+    #[inline]
+    pub fn depth_stencil(mut self, format: RenderBufferFormat) -> Self {
+        self.depth_stencil = Some(format);
+        self
+    }
+
+    /// Set the number of samples, `0` for no multisampling.
+    #[inline]
+    pub fn samples(mut self, samples: u32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Build the render target, checking its completeness.
+    pub fn build(self, context: &Context) -> Result<RenderTarget, RenderTargetError> {
+        let frame_buffer = context.new_frame_buffer();
+        let (colors, depth_stencil) = attach(
+            context,
+            &frame_buffer,
+            &self.colors,
+            self.depth_stencil,
+            self.samples,
+            self.size,
+        )?;
+        Ok(RenderTarget {
+            frame_buffer,
+            colors,
+            depth_stencil,
+            color_formats: self.colors,
+            depth_stencil_format: self.depth_stencil,
+            size: self.size,
+            samples: self.samples,
+        })
+    }
+}
+
+/// Allocate the attachments of `frame_buffer` and check its completeness.
+fn attach(
+    context: &Context,
+    frame_buffer: &FrameBuffer,
+    color_formats: &[SizedFormat],
+    depth_stencil_format: Option<RenderBufferFormat>,
+    samples: u32,
+    size: (u32, u32),
+) -> Result<(Vec<Texture>, Option<RenderBuffer>), RenderTargetError> {
+    if size.0 == 0 || size.1 == 0 {
+        return Err(RenderTargetError::Invalid(format!(
+            "Render target of {}x{} is empty",
+            size.0, size.1
+        )));
+    }
+
+    let mut colors = Vec::with_capacity(color_formats.len());
+    let mut attachments = Vec::with_capacity(color_formats.len());
+    for (index, &format) in color_formats.iter().enumerate() {
+        let texture = if samples == 0 {
+            let texture = context.create_texture(TexTarget::Tex2D);
+            texture.storage_2d(1, InternalFormat::Sized(format), size)?;
+            texture.set(TexParam::MinFilter(MinFilter::Linear));
+            texture.set(TexParam::MagFilter(MagFilter::Linear));
+            texture.set(TexParam::WrapS(Wrap::ClampToEdge));
+            texture.set(TexParam::WrapT(Wrap::ClampToEdge));
+            texture
+        } else {
+            let texture = context.create_texture(TexTarget::Tex2DMultisample);
+            texture.storage_2d_multisample(samples, InternalFormat::Sized(format), size, true)?;
+            texture
+        };
+        let attachment = FrameBufferAttachment::Color(index as _);
+        frame_buffer.attach_texture(attachment, &texture, 0)?;
+        attachments.push(Some(attachment));
+        colors.push(texture);
+    }
+    frame_buffer.draw_buffers(&attachments)?;
+
+    let depth_stencil = match depth_stencil_format {
+        Some(format) => {
+            let attachment = format.depth_stencil_attachment().ok_or_else(|| {
+                RenderTargetError::Invalid(format!("{:?} is not a depth or stencil format", format))
+            })?;
+            let render_buffer = context.new_render_buffer();
+            render_buffer.storage_multisample(samples, format, size)?;
+            frame_buffer.attach_render_buffer(attachment, &render_buffer)?;
+            Some(render_buffer)
+        }
+        None => None,
+    };
+
+    frame_buffer.check()?;
+    Ok((colors, depth_stencil))
+}
@@ -0,0 +1,186 @@
+use std::{
+    cell::RefCell,
+    ops::Deref,
+    rc::{Rc, Weak},
+};
+
+use crate::{Context, SizedFormat};
+
+use super::RenderTarget;
+
+/// Description of the render targets a pool can hand out for each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RenderTargetKey {
+    pub size: (u32, u32),
+    pub format: SizedFormat,
+    pub samples: u32,
+}
+
+impl RenderTargetKey {
+    /// Return the nominal byte size of a render target of this key.
+    #[inline]
+    pub fn bytes(&self) -> usize {
+        self.size.0 as usize
+            * self.size.1 as usize
+            * self.format.texel_bytes()
+            * self.samples.max(1) as usize
+    }
+}
+
+struct Pooled {
+    key: RenderTargetKey,
+    target: RenderTarget,
+    last_used: u64,
+}
+
+struct Inner {
+    free: Vec<Pooled>,
+    frame: u64,
+    max_age: u64,
+    leased: usize,
+    leased_bytes: usize,
+}
+
+/// Pool of transient single color render targets, reused across frames.
+///
+/// Targets not leased for more than `max_age` frames are released by `end_frame()`.
+pub struct RenderTargetPool {
+    inner: Rc<RefCell<Inner>>,
+}
+
+/// Render target leased from a `RenderTargetPool`, given back on drop.
+pub struct RenderTargetLease {
+    key: RenderTargetKey,
+    target: Option<RenderTarget>,
+    pool: Weak<RefCell<Inner>>,
+}
+
+impl RenderTargetPool {
+    /// Create an empty pool releasing targets unused for more than `max_age` frames.
+    #[inline]
+    pub fn new(max_age: u64) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(Inner {
+                free: Vec::new(),
+                frame: 0,
+                max_age,
+                leased: 0,
+                leased_bytes: 0,
+            })),
+        }
+    }
+
+    /// Lease a render target of `size` with one color texture of `format`,
+    /// `samples` is `0` for no multisampling.
+    ///
+    /// A free target of the same key is reused, otherwise a new one is created.
+    pub fn acquire(
+        &self,
+        context: &Context,
+        size: (u32, u32),
+        format: SizedFormat,
+        samples: u32,
+    ) -> Result<RenderTargetLease, String> {
+        let key = RenderTargetKey {
+            size,
+            format,
+            samples,
+        };
+        let mut inner = self.inner.borrow_mut();
+        let reused = inner
+            .free
+            .iter()
+            .position(|pooled| pooled.key == key)
+            .map(|index| inner.free.swap_remove(index).target);
+        let target = match reused {
+            Some(target) => target,
+            None => RenderTarget::builder()
+                .size(size.0, size.1)
+                .color(format)
+                .samples(samples)
+                .build(context)
+                .map_err(|err| err.to_string())?,
+        };
+        inner.leased += 1;
+        inner.leased_bytes += key.bytes();
+        Ok(RenderTargetLease {
+            key,
+            target: Some(target),
+            pool: Rc::downgrade(&self.inner),
+        })
+    }
+
+    /// Advance the frame counter, and release the free targets which are too old.
+    pub fn end_frame(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.frame += 1;
+        let (frame, max_age) = (inner.frame, inner.max_age);
+        inner
+            .free
+            .retain(|pooled| frame - pooled.last_used <= max_age);
+    }
+
+    /// Release all free targets.
+    #[inline]
+    pub fn clear(&self) {
+        self.inner.borrow_mut().free.clear();
+    }
+
+    /// Return the nominal bytes used by the free and the leased targets.
+    #[inline]
+    pub fn memory_usage(&self) -> usize {
+        let inner = self.inner.borrow();
+        inner.leased_bytes
+            + inner
+                .free
+                .iter()
+                .map(|pooled| pooled.key.bytes())
+                .sum::<usize>()
+    }
+
+    /// Return the number of free targets.
+    #[inline]
+    pub fn free_count(&self) -> usize {
+        self.inner.borrow().free.len()
+    }
+
+    /// Return the number of leased targets.
+    #[inline]
+    pub fn leased_count(&self) -> usize {
+        self.inner.borrow().leased
+    }
+}
+
+impl RenderTargetLease {
+    /// Return the key of the leased target.
+    #[inline]
+    pub fn key(&self) -> RenderTargetKey {
+        self.key
+    }
+}
+
+impl Deref for RenderTargetLease {
+    type Target = RenderTarget;
+
+    #[inline]
+    fn deref(&self) -> &RenderTarget {
+        self.target.as_ref().unwrap()
+    }
+}
+
+impl Drop for RenderTargetLease {
+    fn drop(&mut self) {
+        let (Some(pool), Some(target)) = (self.pool.upgrade(), self.target.take()) else {
+            return;
+        };
+        let mut inner = pool.borrow_mut();
+        inner.leased -= 1;
+        inner.leased_bytes -= self.key.bytes();
+        let last_used = inner.frame;
+        inner.free.push(Pooled {
+            key: self.key,
+            target,
+            last_used,
+        });
+    }
+}
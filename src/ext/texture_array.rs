@@ -0,0 +1,265 @@
+use crate::{Context, ImageFormat, InternalFormat, PixelDataType, SizedFormat, TexTarget, Texture};
+
+use super::ImageData;
+
+/// Array texture whose layers share one size and format,
+/// either a `TexTarget::Tex2DArray` or a `TexTarget::TexCubeMapArray`.
+pub struct TextureArray {
+    texture: Texture,
+    target: TexTarget,
+    size: (u32, u32),
+    layers: u32,
+    levels: u32,
+}
+
+impl TextureArray {
+    /// Create a 2D array texture of `layers` images with `levels` mipmap levels.
+    pub fn new(
+        context: &Context,
+        format: SizedFormat,
+        (width, height, layers): (u32, u32, u32),
+        levels: u32,
+    ) -> Result<Self, String> {
+        Self::with_target(
+            context,
+            TexTarget::Tex2DArray,
+            format,
+            (width, height, layers),
+            levels,
+        )
+    }
+
+    /// Create a cube map array texture of `cubes` cube maps with `levels` mipmap levels.
+    ///
+    /// Layer `i` is the face `i % 6` of the cube map `i / 6`.
+    pub fn new_cube(
+        context: &Context,
+        format: SizedFormat,
+        size: u32,
+        cubes: u32,
+        levels: u32,
+    ) -> Result<Self, String> {
+        Self::with_target(
+            context,
+            TexTarget::TexCubeMapArray,
+            format,
+            (size, size, cubes * 6),
+            levels,
+        )
+    }
+
+    fn with_target(
+        context: &Context,
+        target: TexTarget,
+        format: SizedFormat,
+        (width, height, layers): (u32, u32, u32),
+        levels: u32,
+    ) -> Result<Self, String> {
+        if layers == 0 || levels == 0 {
+            return Err("An array texture needs at least one layer and one level".to_string());
+        }
+        let texture = context.create_texture(target);
+        texture.storage_3d(
+            levels,
+            InternalFormat::Sized(format),
+            (width, height, layers),
+        )?;
+        Ok(Self {
+            texture,
+            target,
+            size: (width, height),
+            layers,
+            levels,
+        })
+    }
+
+    /// Upload `image` to the base level of `layer`.
+    #[inline]
+    pub fn load_layer(&self, layer: u32, image: ImageData) -> Result<(), String> {
+        self.load_layer_level(0, layer, image)
+    }
+
+    /// Upload `image` to the mipmap `level` of `layer`.
+    pub fn load_layer_level(&self, level: u32, layer: u32, image: ImageData) -> Result<(), String> {
+        if layer >= self.layers {
+            return Err(format!(
+                "Layer {} is out of range, the texture has {} layers",
+                layer, self.layers
+            ));
+        }
+        let size = self.level_size(level)?;
+        if image.size() != size {
+            return Err(format!(
+                "Level {} expects an image of {}x{}, but got {}x{}",
+                level,
+                size.0,
+                size.1,
+                image.size().0,
+                image.size().1
+            ));
+        }
+        self.texture.sub_image_3d(
+            level,
+            (0, 0, layer),
+            (size.0, size.1, 1),
+            image.format(),
+            image.type_(),
+            image.data(),
+        )
+    }
+
+    /// Generate the mipmap levels from the base level of every layer.
+    #[inline]
+    pub fn gen_minmap(&self) {
+        self.texture.gen_minmap();
+    }
+
+    /// Bind this texture to its target.
+    #[inline]
+    pub fn bind(&self) {
+        self.texture.bind(self.target);
+    }
+
+    /// Return the texture of this array.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Return the target of this array.
+    #[inline]
+    pub fn target(&self) -> TexTarget {
+        self.target
+    }
+
+    /// Return the size of the base level.
+    #[inline]
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Return the size of the mipmap `level`.
+    ///
+    /// It will return `Err(String)` if `level` is out of range.
+    #[inline]
+    pub fn level_size(&self, level: u32) -> Result<(u32, u32), String> {
+        if level >= self.levels {
+            return Err(format!(
+                "Level {} is out of range, the texture has {} levels",
+                level, self.levels
+            ));
+        }
+        Ok(((self.size.0 >> level).max(1), (self.size.1 >> level).max(1)))
+    }
+
+    /// Return the number of layers.
+    #[inline]
+    pub fn layers(&self) -> u32 {
+        self.layers
+    }
+
+    /// Return the number of mipmap levels.
+    #[inline]
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+}
+
+/// 3D texture, such as a volume or a color lookup table.
+pub struct Texture3D {
+    texture: Texture,
+    size: (u32, u32, u32),
+    levels: u32,
+}
+
+impl Texture3D {
+    /// Create a 3D texture with `levels` mipmap levels.
+    pub fn new(
+        context: &Context,
+        format: SizedFormat,
+        size: (u32, u32, u32),
+        levels: u32,
+    ) -> Result<Self, String> {
+        if levels == 0 {
+            return Err("A 3D texture needs at least one level".to_string());
+        }
+        let texture = context.create_texture(TexTarget::Tex3D);
+        texture.storage_3d(levels, InternalFormat::Sized(format), size)?;
+        Ok(Self {
+            texture,
+            size,
+            levels,
+        })
+    }
+
+    /// Upload the whole base level.
+    #[inline]
+    pub fn load_volume<T>(
+        &self,
+        format: ImageFormat,
+        type_: PixelDataType,
+        data: &[T],
+    ) -> Result<(), String> {
+        self.texture
+            .sub_image_3d(0, (0, 0, 0), self.size, format, type_, data)
+    }
+
+    /// Upload `depth` slices of the base level starting from slice `z`.
+    #[inline]
+    pub fn load_slices<T>(
+        &self,
+        z: u32,
+        depth: u32,
+        format: ImageFormat,
+        type_: PixelDataType,
+        data: &[T],
+    ) -> Result<(), String> {
+        match z.checked_add(depth) {
+            Some(end) if end <= self.size.2 => {}
+            _ => {
+                return Err(format!(
+                    "{} slices from {} are out of range, the texture has {} slices",
+                    depth, z, self.size.2
+                ))
+            }
+        }
+        self.texture.sub_image_3d(
+            0,
+            (0, 0, z),
+            (self.size.0, self.size.1, depth),
+            format,
+            type_,
+            data,
+        )
+    }
+
+    /// Generate the mipmap levels from the base level.
+    #[inline]
+    pub fn gen_minmap(&self) {
+        self.texture.gen_minmap();
+    }
+
+    /// Bind this texture to `TexTarget::Tex3D`.
+    #[inline]
+    pub fn bind(&self) {
+        self.texture.bind(TexTarget::Tex3D);
+    }
+
+    /// Return the texture of this volume.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// Return the size of the base level.
+    #[inline]
+    pub fn size(&self) -> (u32, u32, u32) {
+        self.size
+    }
+
+    /// Return the number of mipmap levels.
+    #[inline]
+    pub fn levels(&self) -> u32 {
+        self.levels
+    }
+}